@@ -0,0 +1,107 @@
+// Channel Fan-In Benchmark: M producer threads into one consumer
+// Sweeps the producer count 1, 2, 4, ... --max-producers over a std mpsc
+// channel (or crossbeam with --crossbeam) to show sender-side contention
+use clap::Parser;
+use harness::in_range;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const MAX_PRODUCERS: usize = 8;
+const MESSAGES_PER_PRODUCER: usize = 1_000_000;
+
+#[derive(Parser)]
+#[command(about = "Rust multi-producer channel fan-in benchmark")]
+struct Args {
+    /// Largest producer count in the sweep
+    #[arg(long, default_value_t = MAX_PRODUCERS, value_parser = in_range(1usize, 1024))]
+    max_producers: usize,
+
+    /// Messages sent by each producer
+    #[arg(long, default_value_t = MESSAGES_PER_PRODUCER, value_parser = in_range(1usize, 1_000_000_000))]
+    messages: usize,
+
+    /// Use crossbeam-channel instead of std::sync::mpsc
+    #[arg(long)]
+    crossbeam: bool,
+
+    /// Run even when built without optimizations (timings will be misleading)
+    #[arg(long)]
+    allow_debug: bool,
+
+    /// Decimal places in printed timings and ratios
+    #[arg(long, default_value_t = 2, value_name = "N", value_parser = in_range(0usize, 9))]
+    precision: usize,
+}
+
+// Both channels are unbounded. Producers are spawned in a scope and this
+// thread is the single consumer: it counts messages until the last sender
+// hangs up, which is why the original sender is dropped before receiving.
+fn fan_in_std(producers: usize, messages: usize) -> (Duration, usize) {
+    let (tx, rx) = std::sync::mpsc::channel::<u64>();
+    let start = Instant::now();
+    let received = thread::scope(|s| {
+        for id in 0..producers as u64 {
+            let tx = tx.clone();
+            s.spawn(move || {
+                for i in 0..messages as u64 {
+                    tx.send(id ^ i).expect("Consumer hung up");
+                }
+            });
+        }
+        drop(tx);
+        rx.iter().count()
+    });
+    (start.elapsed(), received)
+}
+
+fn fan_in_crossbeam(producers: usize, messages: usize) -> (Duration, usize) {
+    let (tx, rx) = crossbeam_channel::unbounded::<u64>();
+    let start = Instant::now();
+    let received = thread::scope(|s| {
+        for id in 0..producers as u64 {
+            let tx = tx.clone();
+            s.spawn(move || {
+                for i in 0..messages as u64 {
+                    tx.send(id ^ i).expect("Consumer hung up");
+                }
+            });
+        }
+        drop(tx);
+        rx.iter().count()
+    });
+    (start.elapsed(), received)
+}
+
+fn main() {
+    let args = Args::parse();
+    harness::check_build_profile(args.allow_debug);
+    let channel = if args.crossbeam { "crossbeam-channel" } else { "std::sync::mpsc" };
+
+    let p = args.precision;
+    println!("Benchmark: Channel Fan-In ({})", channel);
+    println!("Messages/producer: {}", args.messages);
+    println!("{:<10} {:<12} {:<14} {:<10}", "Producers", "Time (ms)", "Messages/sec", "vs 1");
+
+    let mut producers = 1;
+    let mut single_rate = None;
+    while producers <= args.max_producers {
+        let (time, received) = if args.crossbeam {
+            fan_in_crossbeam(producers, args.messages)
+        } else {
+            fan_in_std(producers, args.messages)
+        };
+        let expected = producers * args.messages;
+        assert_eq!(received, expected, "Received {} of {} messages", received, expected);
+
+        let rate = expected as f64 / time.as_secs_f64();
+        let baseline = *single_rate.get_or_insert(rate);
+        println!(
+            "{:<10} {:<12.p$} {:<14.0} {:<10}",
+            producers,
+            time.as_secs_f64() * 1000.0,
+            rate,
+            format!("{:.p$}x", rate / baseline)
+        );
+        producers *= 2;
+    }
+}
@@ -0,0 +1,114 @@
+// Rust Parallel Chunk Size Benchmark
+// Splits the fan-out benchmark's SHA256 worker workload into many small
+// items and runs it with par_iter().with_min_len(k) for k = 1, 2, 4, ...,
+// up to one chunk per worker. with_min_len stops rayon splitting below k
+// items, so small k means many tiny jobs (scheduling overhead dominates) and
+// large k means few big ones (idle workers once the last chunks run). The
+// sequential run is the same items in a plain loop.
+use clap::Parser;
+use harness::in_range;
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::time::{Duration, Instant};
+
+const NUM_WORKERS: usize = 8;
+
+#[derive(Parser)]
+#[command(about = "Rust par_iter with_min_len chunk size sweep")]
+struct Args {
+    /// Items in the parallel iterator
+    #[arg(long, default_value_t = 40_000, value_parser = in_range(NUM_WORKERS, 100_000_000))]
+    items: usize,
+
+    /// Hash updates per item (the work in one item)
+    #[arg(long, default_value_t = 10, value_parser = in_range(1usize, 1_000_000))]
+    hashes_per_item: usize,
+
+    /// Time each chunk size this many times and report the mean
+    #[arg(long, value_name = "K", default_value_t = 3, value_parser = in_range(1usize, 1000))]
+    repeat: usize,
+
+    /// Run even when built without optimizations (timings will be misleading)
+    #[arg(long)]
+    allow_debug: bool,
+
+    /// Decimal places in printed timings and ratios
+    #[arg(long, default_value_t = 2, value_name = "N", value_parser = in_range(0usize, 9))]
+    precision: usize,
+}
+
+// The fan-out worker's kernel for one item, folded to a u64 so the
+// parallel sum can be checked against the sequential one
+fn do_work(item: usize, hashes: usize) -> u64 {
+    let mut hasher = Sha256::new();
+    for i in 0..hashes {
+        hasher.update((item * hashes + i).to_string().as_bytes());
+    }
+    let digest = hasher.finalize();
+    u64::from_le_bytes(digest[..8].try_into().expect("digest has 8 bytes"))
+}
+
+// Mean time of K runs
+fn time_mean(repeat: usize, run: impl Fn() -> u64) -> (Duration, u64) {
+    let mut total = 0;
+    let mut elapsed = Duration::ZERO;
+    for _ in 0..repeat {
+        let start = Instant::now();
+        total = run();
+        elapsed += start.elapsed();
+    }
+    (elapsed / repeat as u32, total)
+}
+
+fn main() {
+    let args = Args::parse();
+    harness::check_build_profile(args.allow_debug);
+    let (items, hashes) = (args.items, args.hashes_per_item);
+
+    let (seq_time, expected) = time_mean(args.repeat, || {
+        (0..items).map(|i| do_work(i, hashes)).fold(0, u64::wrapping_add)
+    });
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(NUM_WORKERS)
+        .build()
+        .expect("Failed to build thread pool");
+    let largest = items / NUM_WORKERS;
+    let sizes: Vec<usize> = (0..).map(|shift| 1 << shift).take_while(|&k| k < largest).chain([largest]).collect();
+    let rows: Vec<(usize, Duration)> = sizes
+        .iter()
+        .map(|&min_len| {
+            let (time, total) = pool.install(|| {
+                time_mean(args.repeat, || {
+                    (0..items)
+                        .into_par_iter()
+                        .with_min_len(min_len)
+                        .map(|i| do_work(i, hashes))
+                        .reduce(|| 0, u64::wrapping_add)
+                })
+            });
+            assert_eq!(total, expected, "with_min_len({}) sum differs from the sequential run", min_len);
+            (min_len, time)
+        })
+        .collect();
+
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    let speedup = |d: Duration| seq_time.as_secs_f64() / d.as_secs_f64();
+    let p = args.precision;
+    println!("Benchmark: Parallel Chunk Size Sweep (SHA256)");
+    println!("Workers: {}", NUM_WORKERS);
+    println!("Items: {} ({} hashes each)", items, hashes);
+    println!("Sequential: {:.p$}ms", ms(seq_time));
+    println!("{:<10} {:<10} {:<12} {:<10}", "min_len", "Max jobs", "Time (ms)", "Speedup");
+    for &(min_len, time) in &rows {
+        println!(
+            "{:<10} {:<10} {:<12.p$} {:<10}",
+            min_len,
+            items.div_ceil(min_len),
+            ms(time),
+            format!("{:.p$}x", speedup(time))
+        );
+    }
+    let (best_len, best_time) = rows.iter().copied().min_by_key(|&(_, time)| time).expect("at least one chunk size");
+    println!("Best: with_min_len({}) at {:.p$}x", best_len, speedup(best_time));
+}
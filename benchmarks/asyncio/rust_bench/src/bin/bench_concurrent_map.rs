@@ -0,0 +1,148 @@
+// Concurrent Map Benchmark: mixed inserts and lookups from N threads
+// against a DashMap (sharded locks) or a single Mutex<HashMap>
+use clap::Parser;
+use dashmap::DashMap;
+use harness::in_range;
+use harness::rng::XorShift64;
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const NUM_THREADS: usize = 8;
+const OPS_PER_THREAD: usize = 200000;
+const PREFILL: u64 = 10000; // keys 0..PREFILL exist before timing starts
+
+#[derive(Parser)]
+#[command(about = "Rust concurrent HashMap benchmark")]
+struct Args {
+    /// Worker threads
+    #[arg(long, default_value_t = NUM_THREADS, value_parser = in_range(1usize, 1024))]
+    threads: usize,
+
+    /// Operations per thread
+    #[arg(long, default_value_t = OPS_PER_THREAD, value_parser = in_range(1usize, 100_000_000))]
+    ops: usize,
+
+    /// Percentage of operations that are lookups (the rest insert new keys)
+    #[arg(long, default_value_t = 90, value_parser = in_range(0u8, 100))]
+    read_ratio: u8,
+
+    /// Use a single Mutex<HashMap> instead of DashMap
+    #[arg(long)]
+    mutex: bool,
+
+    /// Seed for the per-thread operation mix
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    /// Run even when built without optimizations (timings will be misleading)
+    #[arg(long)]
+    allow_debug: bool,
+
+    /// Decimal places in printed timings and ratios
+    #[arg(long, default_value_t = 2, value_name = "N", value_parser = in_range(0usize, 9))]
+    precision: usize,
+}
+
+trait ConcurrentMap: Sync {
+    fn insert(&self, key: u64, value: u64);
+    fn get(&self, key: u64) -> Option<u64>;
+    fn len(&self) -> usize;
+}
+
+impl ConcurrentMap for DashMap<u64, u64> {
+    fn insert(&self, key: u64, value: u64) {
+        DashMap::insert(self, key, value);
+    }
+
+    fn get(&self, key: u64) -> Option<u64> {
+        DashMap::get(self, &key).map(|v| *v)
+    }
+
+    fn len(&self) -> usize {
+        DashMap::len(self)
+    }
+}
+
+impl ConcurrentMap for Mutex<HashMap<u64, u64>> {
+    fn insert(&self, key: u64, value: u64) {
+        self.lock().unwrap().insert(key, value);
+    }
+
+    fn get(&self, key: u64) -> Option<u64> {
+        self.lock().unwrap().get(&key).copied()
+    }
+
+    fn len(&self) -> usize {
+        self.lock().unwrap().len()
+    }
+}
+
+// Each thread inserts keys from its own range above PREFILL and looks up
+// prefilled keys, so every lookup must hit and the final size is exact.
+// Returns (inserts, lookup hits).
+fn worker<M: ConcurrentMap>(map: &M, thread_id: usize, args: &Args) -> (usize, usize) {
+    let mut rng = XorShift64::new(args.seed.wrapping_add(thread_id as u64));
+    let base = PREFILL + (thread_id * args.ops) as u64;
+    let (mut inserts, mut hits) = (0, 0);
+    for i in 0..args.ops {
+        if rng.below(100) < args.read_ratio as u64 {
+            if let Some(v) = map.get(rng.below(PREFILL)) {
+                hits += 1;
+                black_box(v);
+            }
+        } else {
+            map.insert(base + i as u64, i as u64);
+            inserts += 1;
+        }
+    }
+    (inserts, hits)
+}
+
+fn run<M: ConcurrentMap>(map: &M, args: &Args) -> Duration {
+    for key in 0..PREFILL {
+        map.insert(key, key);
+    }
+
+    let start = Instant::now();
+    let counts: Vec<(usize, usize)> = thread::scope(|s| {
+        let handles: Vec<_> = (0..args.threads)
+            .map(|id| s.spawn(move || worker(map, id, args)))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    let elapsed = start.elapsed();
+
+    let inserts: usize = counts.iter().map(|c| c.0).sum();
+    let hits: usize = counts.iter().map(|c| c.1).sum();
+    let lookups = args.threads * args.ops - inserts;
+    assert_eq!(hits, lookups, "Lookup of a prefilled key missed");
+    assert_eq!(
+        map.len(),
+        PREFILL as usize + inserts,
+        "Final entry count does not match inserts"
+    );
+    elapsed
+}
+
+fn main() {
+    let args = Args::parse();
+    harness::check_build_profile(args.allow_debug);
+
+    let (label, elapsed) = if args.mutex {
+        ("Mutex<HashMap>", run(&Mutex::new(HashMap::new()), &args))
+    } else {
+        ("DashMap", run(&DashMap::new(), &args))
+    };
+
+    let total_ops = args.threads * args.ops;
+    let p = args.precision;
+    println!("Benchmark: Concurrent Map ({})", label);
+    println!("Threads:    {}", args.threads);
+    println!("Ops/thread: {}", args.ops);
+    println!("Read ratio: {}%", args.read_ratio);
+    println!("Time:       {:.p$}ms", elapsed.as_secs_f64() * 1000.0);
+    println!("Ops/sec:    {:.0}", total_ops as f64 / elapsed.as_secs_f64());
+}
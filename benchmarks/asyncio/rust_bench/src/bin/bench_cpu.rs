@@ -0,0 +1,400 @@
+// Rust Parallel Scaling Benchmark
+// Measures true parallelism: Sequential vs Parallel speedup
+//
+// --numa-local repeats the parallel run with every rayon worker pinned to the
+// CPUs of NUMA node 0 and prints both speedups. Node CPUs come from
+// /sys/devices/system/node, so it is Linux only; on a single-socket machine
+// (one node) or any other OS it just says so and changes nothing.
+use clap::{Parser, ValueEnum};
+use harness::{in_range, write_prometheus_gauge, Format};
+use rayon::prelude::*;
+use sha2::{Sha256, Digest};
+use std::process;
+use std::time::{Duration, Instant};
+
+const NUM_WORKERS: usize = 8;
+const WORK_PER_WORKER: usize = 50000; // 50K hash iterations per worker
+const FP_WORK_PER_WORKER: usize = WORK_PER_WORKER * 200; // 10M multiply-adds per worker
+
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq)]
+enum Workload {
+    /// SHA256 over stringified counters (integer/bitwise heavy)
+    Sha,
+    /// Dependent f64 multiply-add chain (floating-point bound)
+    Fp,
+}
+
+impl Workload {
+    fn title(self) -> &'static str {
+        match self {
+            Workload::Sha => "SHA256",
+            Workload::Fp => "f64 multiply-add",
+        }
+    }
+
+    fn work_label(self) -> String {
+        match self {
+            Workload::Sha => format!("{} hashes", WORK_PER_WORKER),
+            Workload::Fp => format!("{} multiply-adds", FP_WORK_PER_WORKER),
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(about = "Rust parallel scaling benchmark")]
+struct Args {
+    /// Per-worker kernel
+    #[arg(long, value_enum, default_value_t = Workload::Sha)]
+    workload: Workload,
+
+    /// Time the parallel run at 1, 2, 4, ... NUM_WORKERS threads
+    #[arg(long)]
+    sweep: bool,
+
+    /// Also run with every worker confined to the CPUs of NUMA node 0 and
+    /// compare against default placement (Linux; no-op on one node)
+    #[arg(long, conflicts_with = "sweep")]
+    numa_local: bool,
+
+    /// Output format for the --sweep results
+    #[arg(long, value_enum, default_value_t = Format::Table, requires = "sweep")]
+    format: Format,
+
+    /// Run even when built without optimizations (timings will be misleading)
+    #[arg(long)]
+    allow_debug: bool,
+
+    /// Run the parallel workload once against the same per-worker kernels run
+    /// sequentially, with no timing, on every pool the other flags select;
+    /// exits with status 1 if any disagree (debug builds are allowed)
+    #[arg(long, conflicts_with = "format")]
+    check_only: bool,
+
+    /// Decimal places in printed timings and ratios
+    #[arg(long, default_value_t = 2, value_name = "N", value_parser = in_range(0usize, 9))]
+    precision: usize,
+}
+
+fn digest(worker_id: usize, iterations: usize) -> String {
+    let mut hasher = Sha256::new();
+    for i in 0..iterations {
+        hasher.update((worker_id + i).to_string().as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn do_work(worker_id: usize, iterations: usize) -> usize {
+    digest(worker_id, iterations).len()
+}
+
+fn fp_work(worker_id: usize, iterations: usize) -> f64 {
+    let x = 1.0 + worker_id as f64 * 1e-3;
+    let mut acc = 0.0f64;
+    for _ in 0..iterations {
+        acc = acc * 0.999_999 + x;
+    }
+    acc
+}
+
+// Sequential: 1 worker does ALL work. Returns the time and, for fp, the total
+// the parallel run must reproduce.
+fn run_sequential(workload: Workload) -> (Duration, Option<f64>) {
+    let seq_start = Instant::now();
+    let total = match workload {
+        Workload::Sha => {
+            let _seq_total = do_work(0, NUM_WORKERS * WORK_PER_WORKER);
+            None
+        }
+        Workload::Fp => Some((0..NUM_WORKERS).map(|id| fp_work(id, FP_WORK_PER_WORKER)).sum()),
+    };
+    (seq_start.elapsed(), total)
+}
+
+// Parallel: N workers split work
+fn run_parallel(workload: Workload, expected: Option<f64>) -> Duration {
+    let par_start = Instant::now();
+    match workload {
+        Workload::Sha => {
+            let _par_total: usize = (0..NUM_WORKERS)
+                .into_par_iter()
+                .map(|id| do_work(id, WORK_PER_WORKER))
+                .sum();
+        }
+        Workload::Fp => {
+            let par_total: f64 = (0..NUM_WORKERS)
+                .into_par_iter()
+                .map(|id| fp_work(id, FP_WORK_PER_WORKER))
+                .sum();
+            // Only the summation order differs from the sequential run
+            let expected = expected.expect("fp workload needs a sequential total");
+            assert!(
+                ((par_total - expected) / expected).abs() < 1e-12,
+                "Parallel fp total {} != sequential {}",
+                par_total,
+                expected
+            );
+        }
+    }
+    par_start.elapsed()
+}
+
+// One parallel run of every worker's kernel on the current rayon pool,
+// against the same kernels run in a plain loop
+fn check(workload: Workload) -> Result<(), String> {
+    match workload {
+        Workload::Sha => {
+            let seq: Vec<String> = (0..NUM_WORKERS).map(|id| digest(id, WORK_PER_WORKER)).collect();
+            let par: Vec<String> = (0..NUM_WORKERS).into_par_iter().map(|id| digest(id, WORK_PER_WORKER)).collect();
+            match seq.iter().zip(&par).filter(|(s, p)| s != p).count() {
+                0 => Ok(()),
+                differ => Err(format!("{} of {} worker digests differ from the sequential run", differ, NUM_WORKERS)),
+            }
+        }
+        Workload::Fp => {
+            let seq: f64 = (0..NUM_WORKERS).map(|id| fp_work(id, FP_WORK_PER_WORKER)).sum();
+            let par: f64 = (0..NUM_WORKERS).into_par_iter().map(|id| fp_work(id, FP_WORK_PER_WORKER)).sum();
+            // Only the summation order differs
+            if ((par - seq) / seq).abs() < 1e-12 {
+                Ok(())
+            } else {
+                Err(format!("parallel total {} != sequential {}", par, seq))
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod numa {
+    use std::fs;
+    use std::io;
+    use std::mem;
+
+    /// CPUs of each NUMA node, from sysfs; empty when the kernel exposes none.
+    pub fn nodes() -> Vec<Vec<usize>> {
+        let Ok(entries) = fs::read_dir("/sys/devices/system/node") else {
+            return Vec::new();
+        };
+        let mut nodes: Vec<(usize, Vec<usize>)> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let id = entry.file_name().to_str()?.strip_prefix("node")?.parse().ok()?;
+                let cpulist = fs::read_to_string(entry.path().join("cpulist")).ok()?;
+                Some((id, parse_cpulist(&cpulist)))
+            })
+            .filter(|(_, cpus)| !cpus.is_empty())
+            .collect();
+        nodes.sort();
+        nodes.into_iter().map(|(_, cpus)| cpus).collect()
+    }
+
+    // "0-3,8-11" -> [0, 1, 2, 3, 8, 9, 10, 11]
+    fn parse_cpulist(list: &str) -> Vec<usize> {
+        list.trim()
+            .split(',')
+            .filter_map(|part| {
+                let (lo, hi) = part.split_once('-').unwrap_or((part, part));
+                Some(lo.parse::<usize>().ok()?..=hi.parse::<usize>().ok()?)
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Restricts the calling thread to `cpus`. The mask is sized to the
+    /// highest CPU rather than being a cpu_set_t, whose CPU_SET panics past
+    /// CPU_SETSIZE (1024) CPUs.
+    pub fn pin_current_thread(cpus: &[usize]) -> io::Result<()> {
+        let bits = libc::c_ulong::BITS as usize;
+        let words = cpus.iter().max().map_or(0, |&max| max / bits + 1);
+        let mut mask: Vec<libc::c_ulong> = vec![0; words];
+        for &cpu in cpus {
+            mask[cpu / bits] |= 1 << (cpu % bits);
+        }
+        // SAFETY: the kernel reads `size_of_val(mask)` bytes, all owned by
+        // `mask`, and treats CPUs past the end of it as unset
+        let set = mask.as_ptr().cast::<libc::cpu_set_t>();
+        if unsafe { libc::sched_setaffinity(0, mem::size_of_val(&mask[..]), set) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod numa {
+    pub fn nodes() -> Vec<Vec<usize>> {
+        Vec::new()
+    }
+
+    pub fn pin_current_thread(_cpus: &[usize]) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// A NUM_WORKERS pool whose threads may only use `node`'s CPUs
+fn numa_pool(node: &[usize]) -> rayon::ThreadPool {
+    let cpus = node.to_vec();
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(NUM_WORKERS)
+        .start_handler(move |_| numa::pin_current_thread(&cpus).expect("Failed to pin worker to NUMA node"))
+        .build()
+        .expect("Failed to build thread pool")
+}
+
+// Parallel run on node 0's pool, so no worker touches memory across the
+// interconnect
+fn run_numa_local(workload: Workload, expected: Option<f64>, node: &[usize]) -> Duration {
+    numa_pool(node).install(|| run_parallel(workload, expected))
+}
+
+// (threads, time_ms, speedup, efficiency_percent)
+type SweepRow = (usize, f64, f64, f64);
+// (metric name, help text, column)
+type SweepMetric = (&'static str, &'static str, fn(&SweepRow) -> f64);
+
+// Parallel time at each thread count, relative to the sequential run
+fn sweep(workload: Workload, seq_time: Duration, expected: Option<f64>, format: Format, p: usize) {
+    let mut threads = 1;
+    let mut rows: Vec<SweepRow> = Vec::new();
+    while threads <= NUM_WORKERS {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("Failed to build thread pool");
+        let par_time = pool.install(|| run_parallel(workload, expected));
+        let speedup = seq_time.as_secs_f64() / par_time.as_secs_f64();
+        rows.push((threads, par_time.as_secs_f64() * 1000.0, speedup, speedup / threads as f64 * 100.0));
+        threads *= 2;
+    }
+
+    match format {
+        Format::Table => {
+            println!("Benchmark: Parallel Scaling Sweep ({})", workload.title());
+            println!("Sequential: {:.p$}ms", seq_time.as_secs_f64() * 1000.0);
+            println!("{:<8} {:<12} {:<10} {:<10}", "Threads", "Time (ms)", "Speedup", "Efficiency");
+            for (threads, time_ms, speedup, efficiency) in rows {
+                println!(
+                    "{:<8} {:<12.p$} {:<10} {:<10}",
+                    threads,
+                    time_ms,
+                    format!("{:.p$}x", speedup),
+                    format!("{:.p$}%", efficiency)
+                );
+            }
+        }
+        Format::Csv => {
+            println!("threads,time_ms,speedup,efficiency");
+            for (threads, time_ms, speedup, efficiency) in rows {
+                println!("{},{:.p$},{:.p$},{:.0}", threads, time_ms, speedup, efficiency);
+            }
+        }
+        Format::Prometheus => {
+            let workload = workload.to_possible_value().expect("workload has a name");
+            let metrics: [SweepMetric; 3] = [
+                ("metal0_benchmark_parallel_ms", "Parallel run time in milliseconds", |r| r.1),
+                ("metal0_benchmark_speedup", "Sequential time over parallel time", |r| r.2),
+                ("metal0_benchmark_efficiency_percent", "Speedup per thread in percent", |r| r.3),
+            ];
+            let mut out = std::io::stdout().lock();
+            for (name, help, value) in metrics {
+                let samples: Vec<_> = rows
+                    .iter()
+                    .map(|row| {
+                        let labels = vec![
+                            ("workload", workload.get_name().to_string()),
+                            ("threads", row.0.to_string()),
+                        ];
+                        (labels, value(row))
+                    })
+                    .collect();
+                write_prometheus_gauge(&mut out, name, help, &samples).expect("Failed to write metrics");
+            }
+        }
+        Format::Html | Format::Jsonl | Format::Bencher => unreachable!("rejected in main"),
+    }
+}
+
+// --check-only: prints `check NAME ok` or `check NAME FAILED: ...` for the
+// default pool and each --sweep or --numa-local pool, like a harness suite,
+// and exits 1 if any failed
+fn check_only(args: &Args) -> ! {
+    let pool = |threads: usize| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("Failed to build thread pool")
+    };
+    let mut checks = vec![("parallel".to_string(), check(args.workload))];
+    if args.sweep {
+        let counts = (0..).map(|shift| 1 << shift).take_while(|&threads| threads <= NUM_WORKERS);
+        checks.extend(counts.map(|threads| {
+            (format!("{} threads", threads), pool(threads).install(|| check(args.workload)))
+        }));
+    }
+    if args.numa_local {
+        // As in the timed run, one node means default placement
+        if let [node, _, ..] = &numa::nodes()[..] {
+            checks.push(("NUMA-local".to_string(), numa_pool(node).install(|| check(args.workload))));
+        }
+    }
+    let mut failed = false;
+    for (name, result) in checks {
+        match result {
+            Ok(()) => println!("check {} ok", name),
+            Err(e) => {
+                println!("check {} FAILED: {}", name, e);
+                failed = true;
+            }
+        }
+    }
+    process::exit(if failed { 1 } else { 0 });
+}
+
+fn main() {
+    let args = Args::parse();
+    if args.check_only {
+        check_only(&args);
+    }
+    harness::check_build_profile(args.allow_debug);
+    if matches!(args.format, Format::Html | Format::Jsonl | Format::Bencher) {
+        eprintln!("--sweep supports --format table, csv or prometheus");
+        std::process::exit(2);
+    }
+
+    let (seq_time, expected) = run_sequential(args.workload);
+
+    if args.sweep {
+        sweep(args.workload, seq_time, expected, args.format, args.precision);
+        return;
+    }
+
+    let par_time = run_parallel(args.workload, expected);
+
+    let speedup = seq_time.as_secs_f64() / par_time.as_secs_f64();
+    let efficiency = (speedup / NUM_WORKERS as f64) * 100.0;
+
+    let p = args.precision;
+    println!("Benchmark: Parallel Scaling ({})", args.workload.title());
+    println!("Workers: {}", NUM_WORKERS);
+    println!("Work/worker: {}", args.workload.work_label());
+    println!("Sequential: {:.p$}ms", seq_time.as_secs_f64() * 1000.0);
+    println!("Parallel:   {:.p$}ms", par_time.as_secs_f64() * 1000.0);
+    println!("Speedup:    {:.p$}x", speedup);
+    println!("Efficiency: {:.p$}%", efficiency);
+
+    if args.numa_local {
+        let nodes = numa::nodes();
+        if nodes.len() < 2 {
+            println!("NUMA-local: single NUMA node, same as default placement");
+            return;
+        }
+        let local_time = run_numa_local(args.workload, expected, &nodes[0]);
+        let local_speedup = seq_time.as_secs_f64() / local_time.as_secs_f64();
+        println!(
+            "NUMA-local (node 0 of {}, {} CPUs): {:.p$}ms",
+            nodes.len(),
+            nodes[0].len(),
+            local_time.as_secs_f64() * 1000.0
+        );
+        println!("NUMA-local speedup: {:.p$}x (default placement {:.p$}x)", local_speedup, speedup);
+    }
+}
@@ -0,0 +1,124 @@
+// File I/O Benchmark: Concurrent file reads (Rust tokio::fs)
+// Measures the async file-I/O path rather than simulated sleep latency
+//
+// Opens and reads are retried on transient errors (--retries, default 3); a
+// file that still fails counts as failed instead of aborting the run
+use clap::Parser;
+use harness::in_range;
+use harness::retry::Retries;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, BufReader};
+
+const NUM_FILES: usize = 1000;
+const FILE_SIZE: usize = 64 * 1024; // 64KB per file
+const READ_CHUNK: usize = 4096; // bytes requested per read call
+
+#[derive(Parser)]
+#[command(about = "Rust tokio concurrent file read benchmark")]
+struct Args {
+    /// Issue every READ_CHUNK read against the file instead of through a BufReader
+    #[arg(long)]
+    unbuffered: bool,
+
+    /// Retries per open or read after a transient error
+    #[arg(long, default_value_t = 3, value_parser = in_range(0usize, 100))]
+    retries: usize,
+
+    /// Decimal places in printed timings and ratios
+    #[arg(long, default_value_t = 2, value_name = "N", value_parser = in_range(0usize, 9))]
+    precision: usize,
+}
+
+fn create_files(dir: &Path) -> Vec<PathBuf> {
+    std::fs::create_dir_all(dir).expect("Failed to create temp dir");
+    let contents: Vec<u8> = (0..FILE_SIZE).map(|i| (i % 251) as u8).collect();
+    (0..NUM_FILES)
+        .map(|i| {
+            let path = dir.join(format!("file_{}.bin", i));
+            std::fs::write(&path, &contents).expect("Failed to write temp file");
+            path
+        })
+        .collect()
+}
+
+async fn read_all<R: AsyncRead + Unpin>(mut reader: R, retries: &Retries) -> io::Result<u64> {
+    let mut chunk = [0u8; READ_CHUNK];
+    let mut total = 0u64;
+    loop {
+        let mut attempt = 0;
+        let n = loop {
+            match reader.read(&mut chunk).await {
+                Err(e) if retries.should_retry(&e, &mut attempt) => continue,
+                result => break result?,
+            }
+        };
+        if n == 0 {
+            return Ok(total);
+        }
+        total += n as u64;
+    }
+}
+
+async fn worker(path: PathBuf, buffered: bool, retries: Arc<Retries>) -> io::Result<u64> {
+    let file = retries.run_async(|| File::open(&path)).await?;
+    if buffered {
+        read_all(BufReader::new(file), &retries).await
+    } else {
+        read_all(file, &retries).await
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    let buffered = !args.unbuffered;
+    let retries = Arc::new(Retries::new(args.retries));
+
+    let dir = std::env::temp_dir().join(format!("bench_file_io_{}", std::process::id()));
+    let paths = create_files(&dir);
+
+    let start = Instant::now();
+
+    // Spawn all tasks
+    let handles: Vec<_> = paths
+        .into_iter()
+        .map(|path| tokio::spawn(worker(path, buffered, Arc::clone(&retries))))
+        .collect();
+
+    // Collect results
+    let mut total_bytes: u64 = 0;
+    let mut failed = 0;
+    for handle in handles {
+        match handle.await.unwrap() {
+            Ok(bytes) => total_bytes += bytes,
+            Err(e) => {
+                eprintln!("File read failed: {}", e);
+                failed += 1;
+            }
+        }
+    }
+
+    let elapsed = start.elapsed();
+    std::fs::remove_dir_all(&dir).expect("Failed to remove temp dir");
+
+    assert_eq!(total_bytes, ((NUM_FILES - failed) * FILE_SIZE) as u64, "Short read");
+
+    let mb = total_bytes as f64 / (1024.0 * 1024.0);
+    let p = args.precision;
+    println!("Benchmark: File I/O");
+    println!("Files: {}", NUM_FILES);
+    println!("File size: {}KB", FILE_SIZE / 1024);
+    println!("Reads: {} ({}B chunks)", if buffered { "buffered" } else { "unbuffered" }, READ_CHUNK);
+    println!("Total bytes: {}", total_bytes);
+    println!("Transient retries: {} (up to {} per operation)", retries.count(), retries.max());
+    if failed > 0 {
+        println!("Failed files: {} of {}", failed, NUM_FILES);
+    }
+    println!("Time: {:.p$}ms", elapsed.as_secs_f64() * 1000.0);
+    println!("Files/sec: {:.0}", NUM_FILES as f64 / elapsed.as_secs_f64());
+    println!("Throughput: {:.p$} MB/s", mb / elapsed.as_secs_f64());
+}
@@ -0,0 +1,125 @@
+// I/O-Bound Benchmark: Concurrent Sleep (Rust tokio)
+use clap::Parser;
+use harness::in_range;
+use harness::stats::percentile;
+use std::time::Instant;
+use tokio::time::{sleep, Duration};
+
+const NUM_TASKS: i64 = 10000;
+const SLEEP_MS: u64 = 100;  // 100ms simulated I/O latency
+const HISTOGRAM_BUCKETS: usize = 10;
+
+#[derive(Parser)]
+#[command(about = "Rust tokio concurrent sleep benchmark")]
+struct Args {
+    /// Print per-task completion latency percentiles and a histogram
+    #[arg(long)]
+    latency: bool,
+
+    /// Run on a runtime with this many worker threads (default: one per core)
+    /// and report how evenly the oversubscribed tasks complete
+    #[arg(long, value_name = "N", value_parser = in_range(1usize, 1024))]
+    worker_threads: Option<usize>,
+
+    /// Decimal places in printed timings and ratios
+    #[arg(long, default_value_t = 2, value_name = "N", value_parser = in_range(0usize, 9))]
+    precision: usize,
+}
+
+// Returns the task id and its completion time relative to the shared start
+async fn worker(task_id: i64, start: Instant) -> (i64, Duration) {
+    sleep(Duration::from_millis(SLEEP_MS)).await;
+    (task_id, start.elapsed())
+}
+
+// Spread of completion times across tasks: a fair scheduler finishes every
+// task within a narrow window after the shared sleep expires
+fn print_fairness(latencies: &[Duration], p: usize) {
+    let ms: Vec<f64> = latencies.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+    let min = ms.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = ms.iter().copied().fold(0.0, f64::max);
+    let mean = ms.iter().sum::<f64>() / ms.len() as f64;
+    let stddev = (ms.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / ms.len() as f64).sqrt();
+    println!("Completion spread: {:.p$}ms ({:.p$}ms - {:.p$}ms)", max - min, min, max);
+    println!("Completion stddev: {:.p$}ms", stddev);
+}
+
+fn print_latency(latencies: &mut [Duration], p: usize) {
+    latencies.sort();
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+
+    println!("Latency p50:   {:.p$}ms", ms(percentile(latencies, 50.0)));
+    println!("Latency p90:   {:.p$}ms", ms(percentile(latencies, 90.0)));
+    println!("Latency p99:   {:.p$}ms", ms(percentile(latencies, 99.0)));
+    println!("Latency p99.9: {:.p$}ms", ms(percentile(latencies, 99.9)));
+
+    // Equal-width buckets between the fastest and slowest completion
+    let (min, max) = (ms(latencies[0]), ms(latencies[latencies.len() - 1]));
+    let width = ((max - min) / HISTOGRAM_BUCKETS as f64).max(f64::EPSILON);
+    let mut counts = [0usize; HISTOGRAM_BUCKETS];
+    for &latency in latencies.iter() {
+        let bucket = ((ms(latency) - min) / width) as usize;
+        counts[bucket.min(HISTOGRAM_BUCKETS - 1)] += 1;
+    }
+    let peak = counts.iter().copied().max().unwrap_or(1);
+    for (i, count) in counts.iter().enumerate() {
+        let lo = min + width * i as f64;
+        let bar = "#".repeat(count * 40 / peak);
+        println!("  {:>8.p$}-{:>8.p$}ms {:>6} {}", lo, lo + width, count, bar);
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    if let Some(threads) = args.worker_threads {
+        builder.worker_threads(threads);
+    }
+    let runtime = builder.enable_all().build().expect("Failed to build tokio runtime");
+    runtime.block_on(run(args));
+}
+
+async fn run(args: Args) {
+    let start = Instant::now();
+
+    // Spawn all tasks
+    let handles: Vec<_> = (0..NUM_TASKS)
+        .map(|i| tokio::spawn(worker(i, start)))
+        .collect();
+
+    // Collect results
+    let mut total: i64 = 0;
+    let mut latencies = Vec::with_capacity(NUM_TASKS as usize);
+    for handle in handles {
+        let (task_id, latency) = handle.await.unwrap();
+        total += task_id;
+        latencies.push(latency);
+    }
+
+    let elapsed = start.elapsed();
+    // Earliest and latest task completion: a runtime that streams completions
+    // has a first completion close to SLEEP_MS, not close to the total time
+    let first = latencies.iter().min().copied().unwrap_or_default();
+    let last = latencies.iter().max().copied().unwrap_or_default();
+
+    let p = args.precision;
+    println!("Benchmark: I/O-bound");
+    println!("Tasks: {}", NUM_TASKS);
+    println!("Sleep per task: {}ms", SLEEP_MS);
+    println!("Total result: {}", total);
+    println!("Time: {:.p$}ms", elapsed.as_secs_f64() * 1000.0);
+    println!("First completion: {:.p$}ms", first.as_secs_f64() * 1000.0);
+    println!("Last completion: {:.p$}ms", last.as_secs_f64() * 1000.0);
+    println!("Tasks/sec: {:.0}", NUM_TASKS as f64 / elapsed.as_secs_f64());
+    println!("Sequential would be: {}ms", NUM_TASKS as u64 * SLEEP_MS);
+    println!("Concurrency factor: {:.0}x", (NUM_TASKS as u64 * SLEEP_MS) as f64 / (elapsed.as_secs_f64() * 1000.0));
+
+    if let Some(threads) = args.worker_threads {
+        println!("Worker threads: {} ({} tasks per thread)", threads, NUM_TASKS as usize / threads);
+        print_fairness(&latencies, p);
+    }
+
+    if args.latency {
+        print_latency(&mut latencies, p);
+    }
+}
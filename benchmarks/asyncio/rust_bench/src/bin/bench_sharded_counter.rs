@@ -0,0 +1,74 @@
+// Sharded Counter Benchmark: one contended AtomicU64 vs one cache-line-padded
+// atomic per thread, summed at the end
+use clap::Parser;
+use harness::in_range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const NUM_THREADS: usize = 8;
+const OPS_PER_THREAD: usize = 5_000_000;
+
+#[derive(Parser)]
+#[command(about = "Rust contended vs sharded counter benchmark")]
+struct Args {
+    /// Worker threads
+    #[arg(long, default_value_t = NUM_THREADS, value_parser = in_range(1usize, 1024))]
+    threads: usize,
+
+    /// Increments per thread
+    #[arg(long, default_value_t = OPS_PER_THREAD, value_parser = in_range(1usize, 1_000_000_000))]
+    ops: usize,
+
+    /// Run even when built without optimizations (timings will be misleading)
+    #[arg(long)]
+    allow_debug: bool,
+
+    /// Decimal places in printed timings and ratios
+    #[arg(long, default_value_t = 2, value_name = "N", value_parser = in_range(0usize, 9))]
+    precision: usize,
+}
+
+// One per cache line so neighbouring shards never false-share
+#[repr(align(64))]
+struct Shard(AtomicU64);
+
+fn run<'a>(threads: usize, ops: usize, counter: impl Fn(usize) -> &'a AtomicU64 + Sync) -> Duration {
+    let start = Instant::now();
+    thread::scope(|s| {
+        for id in 0..threads {
+            let counter = &counter;
+            s.spawn(move || {
+                let slot = counter(id);
+                for _ in 0..ops {
+                    slot.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn main() {
+    let args = Args::parse();
+    harness::check_build_profile(args.allow_debug);
+    let expected = (args.threads * args.ops) as u64;
+
+    let single = AtomicU64::new(0);
+    let single_time = run(args.threads, args.ops, |_| &single);
+    assert_eq!(single.load(Ordering::Relaxed), expected, "Contended counter lost increments");
+
+    let shards: Vec<Shard> = (0..args.threads).map(|_| Shard(AtomicU64::new(0))).collect();
+    let sharded_time = run(args.threads, args.ops, |id| &shards[id].0);
+    let sharded_total: u64 = shards.iter().map(|s| s.0.load(Ordering::Relaxed)).sum();
+    assert_eq!(sharded_total, expected, "Sharded counter lost increments");
+
+    let ops_per_sec = |d: Duration| expected as f64 / d.as_secs_f64();
+    let p = args.precision;
+    println!("Benchmark: Sharded Counter");
+    println!("Threads: {}", args.threads);
+    println!("Increments/thread: {}", args.ops);
+    println!("Single AtomicU64: {:.p$}ms ({:.0} ops/sec)", single_time.as_secs_f64() * 1000.0, ops_per_sec(single_time));
+    println!("Sharded (padded): {:.p$}ms ({:.0} ops/sec)", sharded_time.as_secs_f64() * 1000.0, ops_per_sec(sharded_time));
+    println!("Speedup: {:.p$}x", single_time.as_secs_f64() / sharded_time.as_secs_f64());
+}
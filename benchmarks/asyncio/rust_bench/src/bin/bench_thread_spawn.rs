@@ -0,0 +1,89 @@
+// Thread Spawn Benchmark: raw std::thread spawn/join cost next to tokio task
+// spawn/join, with no pool in between. Both spawn --batch at a time and join
+// the batch before spawning more, so the number of live threads stays fixed
+// and the process thread limit is never what gets measured.
+use clap::Parser;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::hint::black_box;
+use std::thread;
+
+const ITERATIONS: usize = 10;
+const NUM_SPAWNS: usize = 10000;
+
+#[derive(Parser)]
+#[command(about = "Rust thread vs tokio task spawn overhead benchmark")]
+struct Args {
+    /// Threads (and tasks) to spawn per iteration
+    #[arg(long, default_value_t = NUM_SPAWNS, value_parser = in_range(1usize, 100_000))]
+    spawns: usize,
+
+    /// Spawn this many before joining them, so at most this many threads
+    /// are alive at once
+    #[arg(long, default_value_t = 64, value_parser = in_range(1usize, 1024))]
+    batch: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// Trivial work, so the timing is all spawn and join
+fn work(id: usize) -> usize {
+    black_box(id).wrapping_mul(31)
+}
+
+fn expected(spawns: usize) -> usize {
+    (0..spawns).map(work).fold(0, usize::wrapping_add)
+}
+
+// One thread per id, `batch` at a time, each batch joined before the next
+// is spawned
+fn spawn_threads(spawns: usize, batch: usize) -> usize {
+    let mut total = 0;
+    for first in (0..spawns).step_by(batch) {
+        let handles: Vec<_> = (first..spawns.min(first + batch)).map(|id| thread::spawn(move || work(id))).collect();
+        total = handles.into_iter().map(|h| h.join().unwrap()).fold(total, usize::wrapping_add);
+    }
+    total
+}
+
+// The same batches as tokio tasks
+async fn spawn_tasks(spawns: usize, batch: usize) -> usize {
+    let mut total = 0usize;
+    for first in (0..spawns).step_by(batch) {
+        let handles: Vec<_> = (first..spawns.min(first + batch)).map(|id| tokio::spawn(async move { work(id) })).collect();
+        for handle in handles {
+            total = total.wrapping_add(handle.await.unwrap());
+        }
+    }
+    total
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let (spawns, batch) = (args.spawns, args.batch);
+    let runtime = tokio::runtime::Runtime::new().expect("Failed to build tokio runtime");
+
+    assert_eq!(spawn_threads(spawns, batch), expected(spawns), "Thread results lost");
+    assert_eq!(runtime.block_on(spawn_tasks(spawns, batch)), expected(spawns), "Task results lost");
+
+    let threads = Bencher::new(opts, ITERATIONS).warmup(1).run(|| {
+        black_box(spawn_threads(spawns, batch));
+    });
+    let tasks = Bencher::new(opts, ITERATIONS).warmup(1).run(|| {
+        black_box(runtime.block_on(spawn_tasks(spawns, batch)));
+    });
+
+    let p = opts.precision;
+    let ms = |m: &Measurement| m.per_iter().as_secs_f64() * 1000.0;
+    let per_sec = |m: &Measurement| spawns as f64 * m.ops_per_sec();
+    let per_us = |m: &Measurement| m.per_iter().as_secs_f64() * 1_000_000.0 / spawns as f64;
+    println!("Benchmark: Spawn/join overhead");
+    println!("Spawns: {} per iteration, joined in batches of {}", spawns, batch);
+    println!("Iterations: {}", ITERATIONS);
+    println!("std::thread: {:.p$}ms ({:.0} spawns/sec, {:.p$}µs each)", ms(&threads), per_sec(&threads), per_us(&threads));
+    println!("tokio task:  {:.p$}ms ({:.0} spawns/sec, {:.p$}µs each)", ms(&tasks), per_sec(&tasks), per_us(&tasks));
+    println!("Thread/task cost ratio: {:.p$}x", ms(&threads) / ms(&tasks));
+    threads.print_notes("std::thread", p);
+    tasks.print_notes("tokio task", p);
+}
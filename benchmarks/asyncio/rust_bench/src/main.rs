@@ -0,0 +1,283 @@
+// Rust Parallel Scaling Benchmark
+// Measures true parallelism: Sequential vs Parallel speedup
+
+use clap::Parser;
+use harness::in_range;
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::process;
+use std::time::{Duration, Instant};
+
+const NUM_WORKERS: usize = 8;
+const WORK_PER_WORKER: usize = 50000; // 50K hash iterations per worker
+const IMBALANCED_TASKS: usize = NUM_WORKERS * 8;
+const HEAVY_FACTOR: usize = 10; // heavy tasks do 10x the work of light ones
+const CAPTURE_ITEMS: usize = 4_000_000;
+const TABLE_SIZE: usize = 4096;
+
+#[derive(Parser)]
+#[command(about = "Rust parallel scaling benchmark")]
+struct Args {
+    /// Also run uneven per-task work, against the same number of equal tasks,
+    /// to exercise rayon's work-stealing
+    #[arg(long)]
+    imbalance: bool,
+
+    /// Seed for the --imbalance work distribution
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    /// Also compare a capture-free fn item against a closure borrowing a
+    /// lookup table, over many cheap items
+    #[arg(long)]
+    capture: bool,
+
+    /// Time the parallel reduction this many times and report min/mean/max
+    #[arg(long, value_name = "K", default_value_t = 1, value_parser = in_range(1usize, 1000))]
+    repeat: usize,
+
+    /// Run even when built without optimizations (timings will be misleading)
+    #[arg(long)]
+    allow_debug: bool,
+
+    /// Run each parallel workload once against the same tasks run
+    /// sequentially, with no timing; exits with status 1 if any disagree
+    /// (debug builds are allowed)
+    #[arg(long, conflicts_with = "repeat")]
+    check_only: bool,
+
+    /// Decimal places in printed timings and ratios
+    #[arg(long, default_value_t = 2, value_name = "N", value_parser = in_range(0usize, 9))]
+    precision: usize,
+}
+
+fn digest(worker_id: usize, iterations: usize) -> String {
+    let mut hasher = Sha256::new();
+    for i in 0..iterations {
+        hasher.update((worker_id + i).to_string().as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn do_work(worker_id: usize, iterations: usize) -> usize {
+    digest(worker_id, iterations).len()
+}
+
+// Seeded per-task hash counts: roughly 1 in 8 tasks is heavy, total matches the balanced run
+fn imbalanced_work(seed: u64) -> Vec<usize> {
+    let mut state = seed.max(1);
+    let weights: Vec<usize> = (0..IMBALANCED_TASKS)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            if state.is_multiple_of(8) { HEAVY_FACTOR } else { 1 }
+        })
+        .collect();
+    split_work(&weights)
+}
+
+// The --imbalance baseline: as many tasks and the same total, all equal
+fn even_work() -> Vec<usize> {
+    split_work(&[1; IMBALANCED_TASKS])
+}
+
+// Splits the balanced run's total in proportion to `weights`
+fn split_work(weights: &[usize]) -> Vec<usize> {
+    let total = NUM_WORKERS * WORK_PER_WORKER;
+    let weight_sum: usize = weights.iter().sum();
+    let mut work: Vec<usize> = weights.iter().map(|w| total * w / weight_sum).collect();
+    work[0] += total - work.iter().sum::<usize>();
+    work
+}
+
+// Runs one task per entry of `work` in parallel
+fn run_tasks(work: &[usize]) -> Duration {
+    let start = Instant::now();
+    let _total: usize = work.par_iter().enumerate().map(|(id, &n)| do_work(id, n)).sum();
+    start.elapsed()
+}
+
+// Filled in at compile time, so reading it needs no initialization check
+// and the fn item and the closure below do the same loads
+static TABLE: [u64; TABLE_SIZE] = lookup_table();
+
+const fn lookup_table() -> [u64; TABLE_SIZE] {
+    let mut table = [0; TABLE_SIZE];
+    let mut i = 0;
+    while i < TABLE_SIZE {
+        table[i] = (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        i += 1;
+    }
+    table
+}
+
+// Capture-free: the table is reached through a static, so `map` gets a
+// zero-sized fn item
+fn mix_static(i: usize) -> u64 {
+    TABLE[i % TABLE_SIZE] ^ TABLE[i.wrapping_mul(7) % TABLE_SIZE]
+}
+
+// One task per entry of `work`, hashed in a plain loop and then in parallel
+fn check_tasks(work: &[usize]) -> Result<(), String> {
+    let seq: Vec<String> = work.iter().enumerate().map(|(id, &n)| digest(id, n)).collect();
+    let par: Vec<String> = work.par_iter().enumerate().map(|(id, &n)| digest(id, n)).collect();
+    match seq.iter().zip(&par).filter(|(s, p)| s != p).count() {
+        0 => Ok(()),
+        differ => Err(format!("{} of {} parallel digests differ from the sequential run", differ, work.len())),
+    }
+}
+
+fn check_capture() -> Result<(), String> {
+    let table = TABLE.to_vec();
+    let seq = (0..CAPTURE_ITEMS).map(mix_static).fold(0, u64::wrapping_add);
+    let fn_total = (0..CAPTURE_ITEMS).into_par_iter().map(mix_static).reduce(|| 0, u64::wrapping_add);
+    let closure_total = (0..CAPTURE_ITEMS)
+        .into_par_iter()
+        .map(|i| table[i % TABLE_SIZE] ^ table[i.wrapping_mul(7) % TABLE_SIZE])
+        .reduce(|| 0, u64::wrapping_add);
+    if fn_total != seq || closure_total != seq {
+        return Err(format!("sequential {}, fn item {}, closure {}", seq, fn_total, closure_total));
+    }
+    Ok(())
+}
+
+// --check-only: prints `check NAME ok` or `check NAME FAILED: ...` per
+// workload, like a harness suite, and exits 1 if any failed
+fn check_only(args: &Args) -> ! {
+    let mut checks = vec![("balanced", check_tasks(&[WORK_PER_WORKER; NUM_WORKERS]))];
+    if args.imbalance {
+        checks.push(("even", check_tasks(&even_work())));
+        checks.push(("imbalanced", check_tasks(&imbalanced_work(args.seed))));
+    }
+    if args.capture {
+        checks.push(("capture", check_capture()));
+    }
+    let mut failed = false;
+    for (name, result) in checks {
+        match result {
+            Ok(()) => println!("check {} ok", name),
+            Err(e) => {
+                println!("check {} FAILED: {}", name, e);
+                failed = true;
+            }
+        }
+    }
+    process::exit(if failed { 1 } else { 0 });
+}
+
+// Mean time of K runs of the same parallel sum
+fn time_sum(repeat: usize, run: impl Fn() -> u64) -> (Duration, u64) {
+    let mut total = 0;
+    let mut elapsed = Duration::ZERO;
+    for _ in 0..repeat {
+        let start = Instant::now();
+        total = run();
+        elapsed += start.elapsed();
+    }
+    (elapsed / repeat as u32, total)
+}
+
+fn main() {
+    let args = Args::parse();
+    if args.check_only {
+        check_only(&args);
+    }
+    harness::check_build_profile(args.allow_debug);
+
+    // Sequential: 1 worker does ALL work
+    let seq_start = Instant::now();
+    let _seq_total = do_work(0, NUM_WORKERS * WORK_PER_WORKER);
+    let seq_time = seq_start.elapsed();
+
+    // Parallel: N workers split work, K times so one scheduler hiccup
+    // doesn't define the result
+    let par_times: Vec<Duration> = (0..args.repeat)
+        .map(|_| {
+            let par_start = Instant::now();
+            let _par_total: usize = (0..NUM_WORKERS)
+                .into_par_iter()
+                .map(|id| do_work(id, WORK_PER_WORKER))
+                .sum();
+            par_start.elapsed()
+        })
+        .collect();
+    let par_time = par_times.iter().sum::<Duration>() / args.repeat as u32;
+
+    let speedup = seq_time.as_secs_f64() / par_time.as_secs_f64();
+    let efficiency = (speedup / NUM_WORKERS as f64) * 100.0;
+
+    let p = args.precision;
+    println!("Benchmark: Parallel Scaling (SHA256)");
+    println!("Workers: {}", NUM_WORKERS);
+    println!("Work/worker: {} hashes", WORK_PER_WORKER);
+    println!("Sequential: {:.p$}ms", seq_time.as_secs_f64() * 1000.0);
+    println!("Parallel:   {:.p$}ms", par_time.as_secs_f64() * 1000.0);
+    if args.repeat > 1 {
+        let ms = |d: &Duration| d.as_secs_f64() * 1000.0;
+        let min = par_times.iter().map(ms).fold(f64::INFINITY, f64::min);
+        let max = par_times.iter().map(ms).fold(0.0, f64::max);
+        println!(
+            "Parallel min/mean/max: {:.p$} / {:.p$} / {:.p$}ms over {} runs",
+            min,
+            ms(&par_time),
+            max,
+            args.repeat
+        );
+    }
+    println!("Speedup:    {:.p$}x", speedup);
+    println!("Efficiency: {:.p$}%", efficiency);
+
+    if args.imbalance {
+        // Same total work and task count as an even split, so only the
+        // per-task weights differ between the two runs
+        let even = even_work();
+        let work = imbalanced_work(args.seed);
+        let mean_work = NUM_WORKERS * WORK_PER_WORKER / IMBALANCED_TASKS;
+        let heavy = work.iter().filter(|&&n| n > mean_work).count();
+
+        let even_time = run_tasks(&even);
+        let imb_time = run_tasks(&work);
+
+        let even_speedup = seq_time.as_secs_f64() / even_time.as_secs_f64();
+        let imb_speedup = seq_time.as_secs_f64() / imb_time.as_secs_f64();
+        println!("Even:       {:.p$}ms ({} equal tasks)", even_time.as_secs_f64() * 1000.0, IMBALANCED_TASKS);
+        println!(
+            "Imbalanced: {:.p$}ms ({} tasks, {} at {}x work, seed {})",
+            imb_time.as_secs_f64() * 1000.0,
+            IMBALANCED_TASKS,
+            heavy,
+            HEAVY_FACTOR,
+            args.seed
+        );
+        println!(
+            "Imbalanced speedup: {:.p$}x ({:.p$}% of even)",
+            imb_speedup,
+            imb_speedup / even_speedup * 100.0
+        );
+    }
+
+    if args.capture {
+        // Same lookups; the closure borrows the table from the stack instead
+        // of reading a static
+        let table: Vec<u64> = TABLE.to_vec();
+        let (fn_time, fn_total) = time_sum(args.repeat, || {
+            (0..CAPTURE_ITEMS).into_par_iter().map(mix_static).reduce(|| 0, u64::wrapping_add)
+        });
+        let (closure_time, closure_total) = time_sum(args.repeat, || {
+            (0..CAPTURE_ITEMS)
+                .into_par_iter()
+                .map(|i| table[i % TABLE_SIZE] ^ table[i.wrapping_mul(7) % TABLE_SIZE])
+                .reduce(|| 0, u64::wrapping_add)
+        });
+        assert_eq!(fn_total, closure_total, "fn item and closure sums differ");
+        println!(
+            "Capture ({} items): fn item {:.p$}ms, borrowing closure {:.p$}ms ({:+.p$}%)",
+            CAPTURE_ITEMS,
+            fn_time.as_secs_f64() * 1000.0,
+            closure_time.as_secs_f64() * 1000.0,
+            (closure_time.as_secs_f64() / fn_time.as_secs_f64() - 1.0) * 100.0
+        );
+    }
+}
@@ -0,0 +1,54 @@
+// Base64 Benchmark: encode and decode throughput over a generated buffer
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 1000;
+
+#[derive(Parser)]
+#[command(about = "Base64 encode/decode throughput benchmark")]
+struct Args {
+    /// Size of the raw buffer in KiB
+    #[arg(long, default_value_t = 1024, value_parser = in_range(1usize, 1024 * 1024))]
+    size_kb: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+
+    let mut rng = XorShift64::new(42);
+    let raw: Vec<u8> = (0..args.size_kb * 1024).map(|_| rng.next_u64() as u8).collect();
+    let encoded = STANDARD.encode(&raw);
+    assert_eq!(STANDARD.decode(&encoded).unwrap(), raw, "decode(encode(x)) != x");
+
+    // Output buffers are reused so the loops measure the codec, not the allocator
+    let mut text = String::with_capacity(encoded.len());
+    let encode = Bencher::new(opts, ITERATIONS).warmup(10).run(|| {
+        text.clear();
+        STANDARD.encode_string(black_box(&raw), &mut text);
+    });
+    let mut bytes = Vec::with_capacity(raw.len());
+    let decode = Bencher::new(opts, ITERATIONS).warmup(10).run(|| {
+        bytes.clear();
+        STANDARD.decode_vec(black_box(&encoded), &mut bytes).unwrap();
+    });
+    assert_eq!(text, encoded, "Reused encode buffer differs");
+    assert_eq!(bytes, raw, "Reused decode buffer differs");
+
+    // Both directions are rated by raw (decoded) bytes
+    let mb = raw.len() as f64 / (1024.0 * 1024.0);
+    let p = opts.precision;
+    println!("Benchmark: Base64 (standard alphabet, padded)");
+    println!("Raw size: {} KiB, encoded: {} bytes", args.size_kb, encoded.len());
+    println!("Encode: {:.p$} MB/s", mb * encode.ops_per_sec());
+    println!("Decode: {:.p$} MB/s", mb * decode.ops_per_sec());
+    encode.print_notes("Encode", p);
+    decode.print_notes("Decode", p);
+}
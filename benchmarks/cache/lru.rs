@@ -0,0 +1,101 @@
+// LRU Cache Benchmark: a Zipf-distributed access stream through lru::LruCache
+// Each access is a get, and a miss inserts the key (evicting the least
+// recently used entry once full), as a read-through cache would
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Options};
+use lru::LruCache;
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+
+const ITERATIONS: usize = 20;
+
+#[derive(Parser)]
+#[command(about = "LRU cache benchmark")]
+struct Args {
+    /// Distinct keys in the access stream
+    #[arg(long, default_value_t = 100_000, value_parser = in_range(1usize, 100_000_000))]
+    keys: usize,
+
+    /// Cache capacity in entries
+    #[arg(long, default_value_t = 10_000, value_parser = in_range(1usize, 100_000_000))]
+    capacity: usize,
+
+    /// Accesses per iteration
+    #[arg(long, default_value_t = 1_000_000, value_parser = in_range(1usize, 100_000_000))]
+    ops: usize,
+
+    /// Zipf exponent: 0 is uniform, around 1 is typical web/cache traffic
+    #[arg(long, default_value_t = 0.99, value_parser = in_range(0.0, 5.0))]
+    skew: f64,
+
+    /// Seed for the access stream
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// Key k (0-based) is drawn with probability proportional to 1 / (k+1)^skew,
+// by binary search over the cumulative weights
+fn zipf_stream(keys: usize, ops: usize, skew: f64, seed: u64) -> Vec<u64> {
+    let mut cumulative = Vec::with_capacity(keys);
+    let mut total = 0.0;
+    for k in 0..keys {
+        total += 1.0 / ((k + 1) as f64).powf(skew);
+        cumulative.push(total);
+    }
+    let mut rng = XorShift64::new(seed);
+    (0..ops)
+        .map(|_| {
+            let target = rng.next_f64() * total;
+            cumulative.partition_point(|&c| c <= target).min(keys - 1) as u64
+        })
+        .collect()
+}
+
+// Runs the stream through a fresh cache and returns the number of hits
+fn run(stream: &[u64], capacity: NonZeroUsize) -> usize {
+    let mut cache = LruCache::new(capacity);
+    let mut hits = 0;
+    for &key in stream {
+        if cache.get(&key).is_some() {
+            hits += 1;
+        } else {
+            cache.put(key, key);
+        }
+    }
+    hits
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let capacity = NonZeroUsize::new(args.capacity).expect("capacity is at least 1");
+
+    // The same seed must give the same stream, and so the same hit count
+    let stream = zipf_stream(args.keys, args.ops, args.skew, args.seed);
+    let hits = run(&stream, capacity);
+    assert_eq!(
+        hits,
+        run(&zipf_stream(args.keys, args.ops, args.skew, args.seed), capacity),
+        "Hit count is not deterministic for seed {}",
+        args.seed
+    );
+
+    let m = Bencher::new(opts, ITERATIONS).warmup(2).run(|| {
+        black_box(run(black_box(&stream), capacity));
+    });
+
+    let p = opts.precision;
+    println!(
+        "Benchmark: LRU cache ({} keys, capacity {}, Zipf skew {}, seed {})",
+        args.keys, args.capacity, args.skew, args.seed
+    );
+    println!("Accesses/iteration: {}", args.ops);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Hit rate: {:.p$}%", hits as f64 / args.ops as f64 * 100.0);
+    println!("Throughput: {:.0} ops/sec", args.ops as f64 * m.ops_per_sec());
+    m.print_notes("Throughput", p);
+}
@@ -0,0 +1,73 @@
+// Clone Benchmark: passing String by clone vs &str in a hot loop
+// Scores every string of a Vec<String> with the same function body, once
+// taking an owned String (so the caller clones each item, as unnecessary
+// `.clone()`s do) and once taking &str
+use clap::Parser;
+use harness::{in_range, Bencher, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 1000;
+
+#[derive(Parser)]
+#[command(about = "Clone vs borrow in a hot loop benchmark")]
+struct Args {
+    /// Strings in the Vec
+    #[arg(long, default_value_t = 10_000, value_parser = in_range(1usize, 10_000_000))]
+    items: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// Same work in both: an FNV-1a hash of the bytes
+fn score(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |h, b| (h ^ b as u64).wrapping_mul(0x100_0000_01b3))
+}
+
+fn score_owned(s: String) -> u64 {
+    score(&s)
+}
+
+fn score_borrowed(s: &str) -> u64 {
+    score(s)
+}
+
+// black_box keeps the clone from being optimized back into a borrow
+fn process_cloning(items: &[String]) -> u64 {
+    items.iter().map(|s| score_owned(black_box(s.clone()))).fold(0, u64::wrapping_add)
+}
+
+fn process_borrowing(items: &[String]) -> u64 {
+    items.iter().map(|s| score_borrowed(black_box(s.as_str()))).fold(0, u64::wrapping_add)
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    // Typical identifier-sized strings, 10 to ~40 bytes
+    let items: Vec<String> = (0..args.items)
+        .map(|i| format!("user-{}-{}", i, "x".repeat(i % 32)))
+        .collect();
+    let bytes: usize = items.iter().map(String::len).sum();
+
+    assert_eq!(process_cloning(&items), process_borrowing(&items), "Scores differ");
+
+    let cloning = Bencher::new(opts, ITERATIONS).warmup(20).run(|| {
+        black_box(process_cloning(black_box(&items)));
+    });
+    let borrowing = Bencher::new(opts, ITERATIONS).warmup(20).run(|| {
+        black_box(process_borrowing(black_box(&items)));
+    });
+
+    let p = opts.precision;
+    let cloning_rate = args.items as f64 * cloning.ops_per_sec();
+    let borrowing_rate = args.items as f64 * borrowing.ops_per_sec();
+
+    println!("Benchmark: Clone vs borrow ({} strings, {} bytes)", args.items, bytes);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Cloning (String param): {:.0} items/sec", cloning_rate);
+    println!("Borrowing (&str param): {:.0} items/sec", borrowing_rate);
+    println!("Clone overhead:         {:.p$}x", borrowing_rate / cloning_rate);
+    cloning.print_notes("Cloning", p);
+    borrowing.print_notes("Borrowing", p);
+}
@@ -0,0 +1,100 @@
+// Deep Clone Benchmark: .clone() of a nested serde_json::Value
+// Builds a tree of JSON objects --depth levels deep with --width children
+// per object, leaves mixing strings, numbers and small arrays (the shape a
+// parsed API response has), then clones it repeatedly. Every node and
+// string is a separate allocation, so one innocent-looking .clone() copies
+// the whole tree.
+use clap::Parser;
+use harness::{check_range, in_range, Bencher, Options};
+use serde_json::{json, Map, Value};
+use std::hint::black_box;
+use std::process;
+
+const ITERATIONS: usize = 200;
+// Each flag is capped alone, but --depth 12 --width 32 would still be 32^12
+// leaves; the tree as a whole is capped too
+const MAX_NODES: usize = 10_000_000;
+// A leaf object: itself, four fields and the three tag strings
+const LEAF_NODES: usize = 8;
+
+#[derive(Parser)]
+#[command(about = "Deep clone of a nested JSON Value benchmark")]
+struct Args {
+    /// Levels of nested objects
+    #[arg(long, default_value_t = 5, value_parser = in_range(1usize, 12))]
+    depth: usize,
+
+    /// Children per object
+    #[arg(long, default_value_t = 5, value_parser = in_range(1usize, 32))]
+    width: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn build(depth: usize, width: usize, id: &mut u64) -> Value {
+    *id += 1;
+    if depth == 0 {
+        return json!({
+            "id": *id,
+            "name": format!("item-{}", id),
+            "score": *id as f64 / 7.0,
+            "tags": ["a", "b", "c"],
+        });
+    }
+    let mut object = Map::new();
+    for i in 0..width {
+        object.insert(format!("child_{}", i), build(depth - 1, width, id));
+    }
+    Value::Object(object)
+}
+
+// count_nodes of the tree build would make, worked out without building it
+fn planned_nodes(depth: usize, width: usize) -> usize {
+    let objects = (0..depth).fold(0usize, |sum, level| sum.saturating_add(width.saturating_pow(level as u32)));
+    objects.saturating_add(width.saturating_pow(depth as u32).saturating_mul(LEAF_NODES))
+}
+
+// Every value in the tree, containers included
+fn count_nodes(value: &Value) -> usize {
+    1 + match value {
+        Value::Array(items) => items.iter().map(count_nodes).sum(),
+        Value::Object(fields) => fields.values().map(count_nodes).sum(),
+        _ => 0,
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let planned = planned_nodes(args.depth, args.width);
+    if let Err(e) = check_range(planned, 1, MAX_NODES) {
+        eprintln!("error: --depth {} --width {}: tree nodes {}", args.depth, args.width, e);
+        process::exit(2);
+    }
+    let tree = build(args.depth, args.width, &mut 0);
+    let nodes = count_nodes(&tree);
+    assert_eq!(nodes, planned, "Tree size differs from the planned size");
+    let bytes = serde_json::to_vec(&tree).expect("tree serializes").len();
+
+    assert_eq!(tree.clone(), tree, "Clone differs from the original");
+
+    let m = Bencher::new(opts, ITERATIONS).warmup(5).run(|| {
+        black_box(black_box(&tree).clone());
+    });
+
+    let p = opts.precision;
+    println!(
+        "Benchmark: Deep clone of a serde_json::Value (depth {}, width {})",
+        args.depth, args.width
+    );
+    println!("Tree: {} nodes, {} bytes as JSON", nodes, bytes);
+    println!("Iterations: {}", ITERATIONS);
+    println!(
+        "Clones:  {:.p$} clones/sec ({:.p$} µs each)",
+        m.ops_per_sec(),
+        m.per_iter().as_secs_f64() * 1_000_000.0
+    );
+    println!("Nodes:   {:.0} nodes/sec", nodes as f64 * m.ops_per_sec());
+    m.print_notes("Clones", p);
+}
@@ -0,0 +1,104 @@
+// Collect Benchmark: collect() vs Vec::with_capacity + extend
+// Builds the same Vec<u64> from an iterator both ways, first from a sized
+// iterator (a mapped range, whose exact size_hint collect already uses to
+// allocate once) and then from a filtered one (size_hint lower bound 0, so
+// collect grows by doubling while with_capacity reserves the known upper
+// bound), plus a collect::<String>() of chars against a pre-sized String.
+// The filtered case is the find_iter().collect() shape in the regex
+// benchmarks.
+use clap::Parser;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 200;
+
+#[derive(Parser)]
+#[command(about = "collect() vs pre-sized collection benchmark")]
+struct Args {
+    /// Elements produced by the iterator
+    #[arg(long, default_value_t = 1_000_000, value_parser = in_range(1usize, 1_000_000_000))]
+    n: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn value(i: u64) -> u64 {
+    i.wrapping_mul(0x9e37_79b9_7f4a_7c15)
+}
+
+fn letter(i: usize) -> char {
+    (b'a' + (i % 26) as u8) as char
+}
+
+// Keeps two thirds of the range
+fn sparse(n: usize) -> impl Iterator<Item = u64> {
+    (0..n as u64).filter(|i| i % 3 != 0).map(value)
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let n = args.n;
+
+    let collect_sized = || (0..n as u64).map(value).collect::<Vec<u64>>();
+    let presized_sized = || {
+        let mut v = Vec::with_capacity(n);
+        v.extend((0..n as u64).map(value));
+        v
+    };
+    let collect_filtered = || sparse(n).collect::<Vec<u64>>();
+    let presized_filtered = || {
+        let mut v = Vec::with_capacity(n);
+        v.extend(sparse(n));
+        v
+    };
+    let collect_string = || (0..n).map(letter).collect::<String>();
+    let presized_string = || {
+        let mut s = String::with_capacity(n);
+        s.extend((0..n).map(letter));
+        s
+    };
+
+    assert_eq!(collect_sized(), presized_sized(), "Sized Vecs differ");
+    assert_eq!(collect_filtered(), presized_filtered(), "Filtered Vecs differ");
+    assert_eq!(collect_string(), presized_string(), "Strings differ");
+
+    let time = |f: &dyn Fn()| Bencher::new(opts, ITERATIONS).warmup(5).run(f);
+    let rows: [(&str, Measurement, Measurement); 3] = [
+        (
+            "Vec, sized iterator",
+            time(&|| drop(black_box(collect_sized()))),
+            time(&|| drop(black_box(presized_sized()))),
+        ),
+        (
+            "Vec, filtered iterator",
+            time(&|| drop(black_box(collect_filtered()))),
+            time(&|| drop(black_box(presized_filtered()))),
+        ),
+        (
+            "String from chars",
+            time(&|| drop(black_box(collect_string()))),
+            time(&|| drop(black_box(presized_string()))),
+        ),
+    ];
+
+    let p = opts.precision;
+    let rate = |m: &Measurement| n as f64 * m.ops_per_sec();
+    println!("Benchmark: collect() vs with_capacity + extend ({} elements)", n);
+    println!("Iterations: {}", ITERATIONS);
+    println!("{:<24} {:<22} {:<22} {:<10}", "Case (elements/sec)", "collect()", "with_capacity", "Pre-sizing speedup");
+    for (case, collected, presized) in &rows {
+        println!(
+            "{:<24} {:<22.0} {:<22.0} {:.p$}x",
+            case,
+            rate(collected),
+            rate(presized),
+            rate(presized) / rate(collected)
+        );
+    }
+    for (case, collected, presized) in &rows {
+        collected.print_notes(&format!("{}, collect()", case), p);
+        presized.print_notes(&format!("{}, with_capacity", case), p);
+    }
+}
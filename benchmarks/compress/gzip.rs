@@ -0,0 +1,125 @@
+// Gzip Benchmark: flate2 compression and decompression throughput
+// Generates --mib of log-like text (seeded words, numbers and timestamps, so
+// it compresses about as well as real server output) and, for each --level,
+// gzips it and gunzips the result. Both directions are reported in MB/s of
+// uncompressed data, next to the compression ratio the level reached.
+// flate2 uses its default pure-Rust miniz_oxide backend here, not the
+// system zlib.
+use clap::Parser;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::hint::black_box;
+use std::io::{Read, Write};
+
+const ITERATIONS: usize = 5;
+
+const WORDS: &[&str] = &[
+    "GET", "POST", "/api/users", "/api/orders", "/static/app.js", "200", "404", "500", "user", "session", "cache",
+    "miss", "hit", "timeout", "retry", "connected", "closed", "request", "response", "latency",
+];
+
+#[derive(Parser)]
+#[command(about = "gzip compression and decompression throughput benchmark")]
+struct Args {
+    /// Uncompressed buffer size in MiB
+    #[arg(long, default_value_t = 8, value_parser = in_range(1usize, 4096))]
+    mib: usize,
+
+    /// Compression levels to run, comma-separated (0 stores, 9 is smallest)
+    #[arg(long = "level", value_name = "LEVEL", value_delimiter = ',', default_values_t = [1, 6, 9], value_parser = in_range(0u32, 9))]
+    levels: Vec<u32>,
+
+    /// Seed for the generated text
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// Lines like "1700000123 GET /api/users 200 latency 37 cache hit"
+fn generate(bytes: usize, seed: u64) -> Vec<u8> {
+    let mut rng = XorShift64::new(seed);
+    let mut out = Vec::with_capacity(bytes + 128);
+    let mut clock = 1_700_000_000u64;
+    while out.len() < bytes {
+        clock += rng.below(3);
+        write!(out, "{}", clock).unwrap();
+        for _ in 0..3 + rng.below(6) {
+            if rng.below(4) == 0 {
+                write!(out, " {}", rng.below(1000)).unwrap();
+            } else {
+                write!(out, " {}", WORDS[rng.below(WORDS.len() as u64) as usize]).unwrap();
+            }
+        }
+        out.push(b'\n');
+    }
+    out.truncate(bytes);
+    out
+}
+
+fn compress(data: &[u8], level: u32) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level));
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn decompress(gz: &[u8], len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(len);
+    GzDecoder::new(gz).read_to_end(&mut out).unwrap();
+    out
+}
+
+struct Row {
+    level: u32,
+    compressed: usize,
+    compress: Measurement,
+    decompress: Measurement,
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let data = generate(args.mib * 1024 * 1024, args.seed);
+
+    let rows: Vec<Row> = args
+        .levels
+        .iter()
+        .map(|&level| {
+            let gz = compress(&data, level);
+            assert!(decompress(&gz, data.len()) == data, "Level {} does not round-trip", level);
+            Row {
+                level,
+                compressed: gz.len(),
+                compress: Bencher::new(opts, ITERATIONS).warmup(1).run(|| {
+                    black_box(compress(black_box(&data), level));
+                }),
+                decompress: Bencher::new(opts, ITERATIONS).warmup(1).run(|| {
+                    black_box(decompress(black_box(&gz), data.len()));
+                }),
+            }
+        })
+        .collect();
+
+    let p = opts.precision;
+    let mb_per_sec = |m: &Measurement| data.len() as f64 / 1e6 * m.ops_per_sec();
+    println!("Benchmark: gzip (flate2) on {} MiB of generated log text", args.mib);
+    println!("Iterations: {}", ITERATIONS);
+    println!("{:<8} {:<18} {:<18} {:<10}", "Level", "Compress MB/s", "Decompress MB/s", "Ratio");
+    for row in &rows {
+        println!(
+            "{:<8} {:<18.p$} {:<18.p$} {:.p$}x",
+            row.level,
+            mb_per_sec(&row.compress),
+            mb_per_sec(&row.decompress),
+            data.len() as f64 / row.compressed as f64
+        );
+    }
+    for row in &rows {
+        row.compress.print_notes(&format!("Level {} compress", row.level), p);
+        row.decompress.print_notes(&format!("Level {} decompress", row.level), p);
+    }
+}
@@ -0,0 +1,69 @@
+// Dispatch Benchmark: Box<dyn Fn> vs a monomorphized generic
+// Runs the same accumulation loop twice, calling a small closure per element
+// once through a vtable and once inlined into a generic instantiation
+use clap::Parser;
+use harness::{Bencher, Options};
+use std::hint::black_box;
+
+const ELEMENTS: i64 = 10000;
+const ITERATIONS: usize = 20000;
+
+#[derive(Parser)]
+#[command(about = "Box<dyn Fn> vs static dispatch benchmark")]
+struct Args {
+    #[command(flatten)]
+    harness: Options,
+}
+
+// The per-element work: cheap enough that the call itself dominates
+fn step(x: i64) -> i64 {
+    x.wrapping_mul(31).wrapping_add(7) ^ (x >> 3)
+}
+
+// Every call goes through the vtable; black_box on the box keeps the
+// optimizer from seeing which closure it holds and devirtualizing
+fn accumulate_dyn(f: &dyn Fn(i64) -> i64, n: i64) -> i64 {
+    let mut acc = 0i64;
+    for i in 0..n {
+        acc = acc.wrapping_add(f(i));
+    }
+    acc
+}
+
+// One copy per closure type, with the closure inlined into the loop
+fn accumulate_static<F: Fn(i64) -> i64>(f: F, n: i64) -> i64 {
+    let mut acc = 0i64;
+    for i in 0..n {
+        acc = acc.wrapping_add(f(i));
+    }
+    acc
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+
+    let boxed: Box<dyn Fn(i64) -> i64> = Box::new(step);
+    let expected = accumulate_static(step, ELEMENTS);
+    assert_eq!(accumulate_dyn(&*boxed, ELEMENTS), expected, "dyn and static totals differ");
+
+    let dynamic = Bencher::new(opts, ITERATIONS).warmup(100).run(|| {
+        black_box(accumulate_dyn(black_box(&*boxed), black_box(ELEMENTS)));
+    });
+    let fixed = Bencher::new(opts, ITERATIONS).warmup(100).run(|| {
+        black_box(accumulate_static(step, black_box(ELEMENTS)));
+    });
+
+    let p = opts.precision;
+    let calls = ELEMENTS as f64;
+    let dyn_ns = dynamic.per_iter().as_secs_f64() * 1e9 / calls;
+    let static_ns = fixed.per_iter().as_secs_f64() * 1e9 / calls;
+
+    println!("Benchmark: Dynamic vs static dispatch ({} calls per iteration)", ELEMENTS);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Box<dyn Fn>:      {:.p$}ns/call", dyn_ns);
+    println!("Generic (static): {:.p$}ns/call", static_ns);
+    println!("Dispatch overhead: {:.p$}ns/call ({:.p$}x)", dyn_ns - static_ns, dyn_ns / static_ns);
+    dynamic.print_notes("Box<dyn Fn>", p);
+    fixed.print_notes("Generic (static)", p);
+}
@@ -0,0 +1,123 @@
+// Dispatch Table Benchmark: match on an opcode vs HashMap<u32, fn> lookup
+// Runs one generated opcode stream through a tiny accumulator VM twice: once
+// through a dense match (compiled to a jump table), once looking each opcode
+// up in a HashMap of handler functions
+use clap::Parser;
+use harness::{in_range, Bencher, Options};
+use std::collections::HashMap;
+use std::hint::black_box;
+
+const OPCODES: u32 = 16;
+const ITERATIONS: usize = 200;
+
+#[derive(Parser)]
+#[command(about = "match vs HashMap dispatch table benchmark")]
+struct Args {
+    /// Opcodes in the generated stream
+    #[arg(long, default_value_t = 1_000_000, value_parser = in_range(1usize, 100_000_000))]
+    ops: usize,
+
+    /// Seed for the opcode stream
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+type Handler = fn(i64) -> i64;
+
+// Handlers indexed by opcode; the match below must agree with this table
+const HANDLERS: [Handler; OPCODES as usize] = [
+    |a| a.wrapping_add(1),
+    |a| a.wrapping_sub(1),
+    |a| a.wrapping_mul(3),
+    |a| a >> 1,
+    |a| a << 1,
+    |a| a ^ 0x5555,
+    |a| a | 0x10,
+    |a| a & !0x10,
+    |a| a.wrapping_add(7),
+    |a| a.wrapping_sub(7),
+    |a| a.rotate_left(3),
+    |a| a.rotate_right(3),
+    |a| !a,
+    |a| a.wrapping_neg(),
+    |a| a.wrapping_add(a >> 4),
+    |a| a % 1_000_003,
+];
+
+fn run_match(program: &[u32]) -> i64 {
+    let mut acc = 1i64;
+    for &op in program {
+        acc = match op {
+            0 => acc.wrapping_add(1),
+            1 => acc.wrapping_sub(1),
+            2 => acc.wrapping_mul(3),
+            3 => acc >> 1,
+            4 => acc << 1,
+            5 => acc ^ 0x5555,
+            6 => acc | 0x10,
+            7 => acc & !0x10,
+            8 => acc.wrapping_add(7),
+            9 => acc.wrapping_sub(7),
+            10 => acc.rotate_left(3),
+            11 => acc.rotate_right(3),
+            12 => !acc,
+            13 => acc.wrapping_neg(),
+            14 => acc.wrapping_add(acc >> 4),
+            15 => acc % 1_000_003,
+            _ => unreachable!("opcode {} out of range", op),
+        };
+    }
+    acc
+}
+
+fn run_hashmap(program: &[u32], table: &HashMap<u32, Handler>) -> i64 {
+    let mut acc = 1i64;
+    for op in program {
+        acc = table[op](acc);
+    }
+    acc
+}
+
+// Seeded uniform opcode stream (xorshift64)
+fn generate(len: usize, seed: u64) -> Vec<u32> {
+    let mut state = seed.max(1);
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % OPCODES as u64) as u32
+        })
+        .collect()
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let program = generate(args.ops, args.seed);
+    let table: HashMap<u32, Handler> = (0..OPCODES).zip(HANDLERS).collect();
+
+    assert_eq!(run_match(&program), run_hashmap(&program, &table), "match and HashMap results differ");
+
+    let matched = Bencher::new(opts, ITERATIONS).warmup(5).run(|| {
+        black_box(run_match(black_box(&program)));
+    });
+    let hashed = Bencher::new(opts, ITERATIONS).warmup(5).run(|| {
+        black_box(run_hashmap(black_box(&program), &table));
+    });
+
+    let p = opts.precision;
+    let match_rate = args.ops as f64 * matched.ops_per_sec();
+    let hash_rate = args.ops as f64 * hashed.ops_per_sec();
+
+    println!("Benchmark: Dispatch table ({} opcodes, {} ops, seed {})", OPCODES, args.ops, args.seed);
+    println!("Iterations: {}", ITERATIONS);
+    println!("match (jump table):   {:.0} ops/sec", match_rate);
+    println!("HashMap<u32, fn>:     {:.0} ops/sec", hash_rate);
+    println!("match speedup: {:.p$}x", match_rate / hash_rate);
+    matched.print_notes("match", p);
+    hashed.print_notes("HashMap<u32, fn>", p);
+}
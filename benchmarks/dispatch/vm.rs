@@ -0,0 +1,229 @@
+// Stack VM Benchmark: enum match vs Box<dyn Instruction> dispatch
+// Runs one generated program through a small stack-machine interpreter
+// twice: once with instructions as an enum matched in the loop, once as
+// trait objects each executing itself through a vtable call. Both share the
+// same stack and the same operation bodies, so the gap is the dispatch.
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 100;
+// The generator keeps the stack between 0 and this many values deep
+const MAX_DEPTH: usize = 32;
+
+#[derive(Parser)]
+#[command(about = "Stack VM enum vs trait-object dispatch benchmark")]
+struct Args {
+    /// Instructions in the generated program
+    #[arg(long, default_value_t = 1_000_000, value_parser = in_range(1usize, 100_000_000))]
+    instructions: usize,
+
+    /// Seed for the program
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Push(i64),
+    Add,
+    Sub,
+    Mul,
+    Xor,
+    Dup,
+    Swap,
+    Pop,
+}
+
+// Operation bodies shared by both interpreters
+fn binary(stack: &mut Vec<i64>, f: fn(i64, i64) -> i64) {
+    let b = stack.pop().expect("stack underflow");
+    let a = stack.pop().expect("stack underflow");
+    stack.push(f(a, b));
+}
+
+fn dup(stack: &mut Vec<i64>) {
+    let top = *stack.last().expect("stack underflow");
+    stack.push(top);
+}
+
+fn swap(stack: &mut [i64]) {
+    let n = stack.len();
+    stack.swap(n - 1, n - 2);
+}
+
+fn pop(stack: &mut Vec<i64>) {
+    stack.pop().expect("stack underflow");
+}
+
+fn run_enum(program: &[Op], stack: &mut Vec<i64>) -> i64 {
+    stack.clear();
+    for op in program {
+        match *op {
+            Op::Push(k) => stack.push(k),
+            Op::Add => binary(stack, i64::wrapping_add),
+            Op::Sub => binary(stack, i64::wrapping_sub),
+            Op::Mul => binary(stack, i64::wrapping_mul),
+            Op::Xor => binary(stack, |a, b| a ^ b),
+            Op::Dup => dup(stack),
+            Op::Swap => swap(stack),
+            Op::Pop => pop(stack),
+        }
+    }
+    stack.iter().fold(0, |sum, &x| sum.wrapping_add(x))
+}
+
+trait Instruction {
+    fn execute(&self, stack: &mut Vec<i64>);
+}
+
+struct Push(i64);
+struct Add;
+struct Sub;
+struct Mul;
+struct Xor;
+struct Dup;
+struct Swap;
+struct Pop;
+
+impl Instruction for Push {
+    fn execute(&self, stack: &mut Vec<i64>) {
+        stack.push(self.0);
+    }
+}
+
+impl Instruction for Add {
+    fn execute(&self, stack: &mut Vec<i64>) {
+        binary(stack, i64::wrapping_add);
+    }
+}
+
+impl Instruction for Sub {
+    fn execute(&self, stack: &mut Vec<i64>) {
+        binary(stack, i64::wrapping_sub);
+    }
+}
+
+impl Instruction for Mul {
+    fn execute(&self, stack: &mut Vec<i64>) {
+        binary(stack, i64::wrapping_mul);
+    }
+}
+
+impl Instruction for Xor {
+    fn execute(&self, stack: &mut Vec<i64>) {
+        binary(stack, |a, b| a ^ b);
+    }
+}
+
+impl Instruction for Dup {
+    fn execute(&self, stack: &mut Vec<i64>) {
+        dup(stack);
+    }
+}
+
+impl Instruction for Swap {
+    fn execute(&self, stack: &mut Vec<i64>) {
+        swap(stack);
+    }
+}
+
+impl Instruction for Pop {
+    fn execute(&self, stack: &mut Vec<i64>) {
+        pop(stack);
+    }
+}
+
+fn boxed(op: Op) -> Box<dyn Instruction> {
+    match op {
+        Op::Push(k) => Box::new(Push(k)),
+        Op::Add => Box::new(Add),
+        Op::Sub => Box::new(Sub),
+        Op::Mul => Box::new(Mul),
+        Op::Xor => Box::new(Xor),
+        Op::Dup => Box::new(Dup),
+        Op::Swap => Box::new(Swap),
+        Op::Pop => Box::new(Pop),
+    }
+}
+
+fn run_dyn(program: &[Box<dyn Instruction>], stack: &mut Vec<i64>) -> i64 {
+    stack.clear();
+    for instruction in program {
+        instruction.execute(stack);
+    }
+    stack.iter().fold(0, |sum, &x| sum.wrapping_add(x))
+}
+
+// A random program that never underflows the stack or grows it past
+// MAX_DEPTH, picking only among the instructions valid at each depth
+fn generate(len: usize, seed: u64) -> Vec<Op> {
+    let mut rng = XorShift64::new(seed);
+    let mut depth = 0usize;
+    (0..len)
+        .map(|_| {
+            let op = loop {
+                let op = match rng.below(8) {
+                    0 => Op::Push(rng.below(1000) as i64),
+                    1 => Op::Add,
+                    2 => Op::Sub,
+                    3 => Op::Mul,
+                    4 => Op::Xor,
+                    5 => Op::Dup,
+                    6 => Op::Swap,
+                    _ => Op::Pop,
+                };
+                let valid = match op {
+                    Op::Push(_) => depth < MAX_DEPTH,
+                    Op::Dup => (1..MAX_DEPTH).contains(&depth),
+                    Op::Add | Op::Sub | Op::Mul | Op::Xor | Op::Swap => depth >= 2,
+                    Op::Pop => depth >= 1,
+                };
+                if valid {
+                    break op;
+                }
+            };
+            depth = match op {
+                Op::Push(_) | Op::Dup => depth + 1,
+                Op::Add | Op::Sub | Op::Mul | Op::Xor | Op::Pop => depth - 1,
+                Op::Swap => depth,
+            };
+            op
+        })
+        .collect()
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let program = generate(args.instructions, args.seed);
+    let objects: Vec<Box<dyn Instruction>> = program.iter().copied().map(boxed).collect();
+    let mut stack = Vec::with_capacity(MAX_DEPTH);
+
+    let expected = run_enum(&program, &mut stack);
+    assert_eq!(run_dyn(&objects, &mut stack), expected, "enum and trait-object VMs differ");
+
+    let enum_m = Bencher::new(opts, ITERATIONS).warmup(5).run(|| {
+        black_box(run_enum(black_box(&program), &mut stack));
+    });
+    let dyn_m = Bencher::new(opts, ITERATIONS).warmup(5).run(|| {
+        black_box(run_dyn(black_box(&objects), &mut stack));
+    });
+
+    let p = opts.precision;
+    let enum_rate = args.instructions as f64 * enum_m.ops_per_sec();
+    let dyn_rate = args.instructions as f64 * dyn_m.ops_per_sec();
+
+    println!("Benchmark: Stack VM dispatch ({} instructions, seed {})", args.instructions, args.seed);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Result: {}", expected);
+    println!("enum + match:          {:.0} instructions/sec", enum_rate);
+    println!("Box<dyn Instruction>:  {:.0} instructions/sec", dyn_rate);
+    println!("enum speedup: {:.p$}x", enum_rate / dyn_rate);
+    enum_m.print_notes("enum + match", p);
+    dyn_m.print_notes("Box<dyn Instruction>", p);
+}
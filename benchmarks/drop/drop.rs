@@ -0,0 +1,59 @@
+// Drop Benchmark: time to deallocate a large nested structure
+// Each iteration builds a Vec<Vec<String>> outside the timer and times only
+// the drop, isolating deallocation cost; the managed-language equivalent is
+// the GC work to reclaim the same graph
+use clap::Parser;
+use harness::{check_range, in_range, Bencher, Options};
+use std::hint::black_box;
+use std::process;
+
+const ITERATIONS: usize = 200;
+// Each flag is capped alone; their product, the strings built per
+// iteration, is capped too
+const MAX_STRINGS: usize = 10_000_000;
+
+#[derive(Parser)]
+#[command(about = "Drop cost of a large nested structure")]
+struct Args {
+    /// Outer Vec length
+    #[arg(long, default_value_t = 1000, value_parser = in_range(1usize, 1_000_000))]
+    rows: usize,
+
+    /// Strings per inner Vec
+    #[arg(long, default_value_t = 100, value_parser = in_range(1usize, 1_000_000))]
+    cols: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn build(rows: usize, cols: usize) -> Vec<Vec<String>> {
+    (0..rows)
+        .map(|r| (0..cols).map(|c| format!("cell-{}-{}", r, c)).collect())
+        .collect()
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    if let Err(e) = check_range(args.rows.saturating_mul(args.cols), 1, MAX_STRINGS) {
+        eprintln!("error: --rows {} --cols {}: strings {}", args.rows, args.cols, e);
+        process::exit(2);
+    }
+    // One allocation per string, one per inner Vec, one for the outer Vec
+    let allocations = args.rows * args.cols + args.rows + 1;
+
+    let m = Bencher::new(opts, ITERATIONS).warmup(5).run_with_setup(
+        || black_box(build(args.rows, args.cols)),
+        std::mem::drop,
+    );
+
+    let p = opts.precision;
+    let per_drop = m.per_iter().as_secs_f64();
+    println!("Benchmark: Drop of Vec<Vec<String>> ({} x {})", args.rows, args.cols);
+    println!("Allocations freed per drop: {}", allocations);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Drop time: {:.p$}µs ({:.0} drops/sec)", per_drop * 1_000_000.0, m.ops_per_sec());
+    println!("Per allocation: {:.p$}ns", per_drop * 1e9 / allocations as f64);
+    m.print_notes("Drop time", p);
+}
@@ -0,0 +1,112 @@
+// Error Handling Benchmark: Result plumbing with ? vs an unchecked variant
+// Runs the same per-element computation twice over a Vec<u32>: once with
+// checked arithmetic and a range check at every step, each returning
+// Result and propagated with ?, and once with wrapping arithmetic and no
+// checks. The inputs never trigger an error, so any gap is the cost of the
+// checks and the Result plumbing on the happy path. Steps are kept out of
+// line so the Result really crosses a call boundary.
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 1000;
+
+// Inputs stay below this, so no step ever overflows
+const LIMIT: u32 = 1 << 16;
+
+#[derive(Parser)]
+#[command(about = "Result/? error propagation overhead benchmark")]
+struct Args {
+    /// Elements per iteration
+    #[arg(long, default_value_t = 100_000, value_parser = in_range(1usize, 100_000_000))]
+    n: usize,
+
+    /// Seed for the inputs
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+#[derive(Debug)]
+enum StepError {
+    OutOfRange(u32),
+    Overflow,
+}
+
+#[inline(never)]
+fn scale(x: u32) -> Result<u64, StepError> {
+    if x >= LIMIT {
+        return Err(StepError::OutOfRange(x));
+    }
+    (x as u64).checked_mul(2_654_435_761).ok_or(StepError::Overflow)
+}
+
+#[inline(never)]
+fn mix(h: u64, v: u64) -> Result<u64, StepError> {
+    let h = (h >> 7).checked_add(v).ok_or(StepError::Overflow)?;
+    Ok(h ^ (h >> 13))
+}
+
+fn checked(inputs: &[u32]) -> Result<u64, StepError> {
+    let mut h = 0u64;
+    for &x in inputs {
+        h = mix(h, scale(x)?)?;
+    }
+    Ok(h)
+}
+
+#[inline(never)]
+fn scale_unchecked(x: u32) -> u64 {
+    (x as u64).wrapping_mul(2_654_435_761)
+}
+
+#[inline(never)]
+fn mix_unchecked(h: u64, v: u64) -> u64 {
+    let h = (h >> 7).wrapping_add(v);
+    h ^ (h >> 13)
+}
+
+fn unchecked(inputs: &[u32]) -> u64 {
+    inputs.iter().fold(0, |h, &x| mix_unchecked(h, scale_unchecked(x)))
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let mut rng = XorShift64::new(args.seed);
+    let inputs: Vec<u32> = (0..args.n).map(|_| rng.below(LIMIT as u64) as u32).collect();
+
+    // Both variants agree, and the checks do fire on bad input
+    let expected = unchecked(&inputs);
+    assert_eq!(checked(&inputs).expect("Valid input rejected"), expected, "Variants differ");
+    assert!(
+        matches!(checked(&[1, LIMIT, 2]), Err(StepError::OutOfRange(LIMIT))),
+        "Range check did not fire"
+    );
+
+    let with_result = Bencher::new(opts, ITERATIONS).warmup(20).run(|| {
+        black_box(checked(black_box(&inputs)).ok());
+    });
+    let without = Bencher::new(opts, ITERATIONS).warmup(20).run(|| {
+        black_box(unchecked(black_box(&inputs)));
+    });
+
+    let p = opts.precision;
+    let result_rate = args.n as f64 * with_result.ops_per_sec();
+    let unchecked_rate = args.n as f64 * without.ops_per_sec();
+
+    println!("Benchmark: Result/? propagation ({} elements, 2 steps each)", args.n);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Result with ?: {:.0} ops/sec", result_rate);
+    println!("Unchecked:     {:.0} ops/sec", unchecked_rate);
+    println!(
+        "Result overhead: {:.p$}x ({:+.p$}%)",
+        unchecked_rate / result_rate,
+        (unchecked_rate / result_rate - 1.0) * 100.0
+    );
+    with_result.print_notes("Result with ?", p);
+    without.print_notes("Unchecked", p);
+}
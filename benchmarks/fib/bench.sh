@@ -24,11 +24,44 @@ EOF
 
 # Rust source
 cat > fib.rs <<'EOF'
+use std::{env, process};
+
+// Keeps --sequence under a few seconds even in a debug build
+const MAX_SEQUENCE: u64 = 1_000_000_000;
+
 fn fib(n: u64) -> u64 {
     if n <= 1 { n } else { fib(n - 1) + fib(n - 2) }
 }
 
+// Iterative fib(0..=n) XOR-folded into one value, so every term must be computed
+fn fib_sequence_checksum(n: u64) -> u64 {
+    let (mut a, mut b) = (0u64, 1u64);
+    let mut checksum = 0u64;
+    for _ in 0..=n {
+        checksum ^= a;
+        let next = a.wrapping_add(b);
+        a = b;
+        b = next;
+    }
+    checksum
+}
+
 fn main() {
+    // Usage: fib_rust [--sequence <n>]
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("--sequence") {
+        let arg = args.get(1).map_or("nothing", String::as_str);
+        let n = match arg.parse::<u64>() {
+            Ok(n) if n <= MAX_SEQUENCE => n,
+            _ => {
+                eprintln!("--sequence must be between 0 and {}, got {}", MAX_SEQUENCE, arg);
+                process::exit(2);
+            }
+        };
+        println!("{}", fib_sequence_checksum(n));
+        return;
+    }
+
     let result = fib(45);
     println!("{}", result);
 }
@@ -0,0 +1,134 @@
+// Recursion Depth Benchmark: how deep a simple recursion can go before it
+// overflows the stack, on a thread::spawn thread with the default stack and on
+// a thread::Builder thread with a large custom stack.
+// Companion to the recursive fib benchmark; managed runtimes with growable
+// stacks (Go) or a recursion limit (Python) behave differently. An overflow
+// aborts the whole process, so every probe runs in a child process and the
+// limit is found by bisection.
+use clap::{Parser, ValueEnum};
+use harness::in_range;
+use std::hint::black_box;
+use std::process::{self, Command, Stdio};
+use std::thread;
+
+// Rust's thread::spawn stack size when RUST_MIN_STACK is not set
+const DEFAULT_SPAWN_STACK: usize = 2 << 20;
+
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq)]
+enum Placement {
+    /// thread::spawn with the default stack
+    Spawned,
+    /// thread::Builder with --stack-mib
+    Custom,
+}
+
+#[derive(Parser)]
+#[command(about = "Maximum recursion depth per thread stack size")]
+struct Args {
+    /// Stack size of the thread::Builder thread, in MiB
+    #[arg(long, default_value_t = 256, value_parser = in_range(1usize, 4096))]
+    stack_mib: usize,
+
+    /// Run even when built without optimizations (frames are much larger)
+    #[arg(long)]
+    allow_debug: bool,
+
+    /// Recurse this deep and exit (internal: used by the bisection)
+    #[arg(long, hide = true, requires = "probe_on")]
+    probe_depth: Option<u64>,
+
+    #[arg(long, hide = true, value_enum)]
+    probe_on: Option<Placement>,
+}
+
+// Not a tail call: the result goes through black_box after the recursive call
+// returns, so each level keeps its frame. Returns the leaf frame's address.
+fn recurse(n: u64) -> usize {
+    let marker = 0u8;
+    if n == 0 {
+        return black_box(&marker) as *const u8 as usize;
+    }
+    black_box(recurse(black_box(n - 1)))
+}
+
+fn spawn_stack() -> usize {
+    std::env::var("RUST_MIN_STACK")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_SPAWN_STACK)
+}
+
+fn run_on(placement: Placement, stack_mib: usize, f: impl FnOnce() -> usize + Send + 'static) -> usize {
+    match placement {
+        Placement::Spawned => thread::spawn(f).join().expect("Probe thread panicked"),
+        Placement::Custom => thread::Builder::new()
+            .stack_size(stack_mib << 20)
+            .spawn(f)
+            .expect("Failed to spawn probe thread")
+            .join()
+            .expect("Probe thread panicked"),
+    }
+}
+
+// Recurses `depth` levels in a fresh process; false if it overflowed
+fn probe(placement: Placement, stack_mib: usize, depth: u64) -> bool {
+    let exe = std::env::current_exe().expect("Failed to locate own executable");
+    let placement = placement.to_possible_value().expect("placement has a name");
+    Command::new(exe)
+        .args(["--probe-depth", &depth.to_string(), "--probe-on", placement.get_name()])
+        .args(["--stack-mib", &stack_mib.to_string(), "--allow-debug"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .expect("Failed to run probe process")
+        .success()
+}
+
+// Largest depth that completes. Every call pushes at least a return address,
+// so a stack of `stack_bytes` cannot hold more than stack_bytes / 8 frames.
+fn max_depth(placement: Placement, stack_mib: usize, stack_bytes: usize) -> u64 {
+    let (mut ok, mut overflow) = (0u64, (stack_bytes / 8) as u64);
+    assert!(!probe(placement, stack_mib, overflow), "Recursion did not overflow; was it turned into a loop?");
+    while overflow - ok > 1 {
+        let mid = ok + (overflow - ok) / 2;
+        if probe(placement, stack_mib, mid) {
+            ok = mid;
+        } else {
+            overflow = mid;
+        }
+    }
+    ok
+}
+
+fn main() {
+    let args = Args::parse();
+    harness::check_build_profile(args.allow_debug);
+
+    if let (Some(depth), Some(placement)) = (args.probe_depth, args.probe_on) {
+        run_on(placement, args.stack_mib, move || recurse(depth));
+        process::exit(0);
+    }
+
+    // Stack bytes per level, from the distance between two leaf frames
+    const SHALLOW: u64 = 1000;
+    let frame_bytes = run_on(Placement::Spawned, args.stack_mib, || {
+        let near = recurse(SHALLOW);
+        let far = recurse(2 * SHALLOW);
+        (near - far) / SHALLOW as usize
+    });
+
+    let custom_stack = args.stack_mib << 20;
+    let rows = [
+        (Placement::Spawned, "thread::spawn", format!("{} KiB", spawn_stack() >> 10), spawn_stack()),
+        (Placement::Custom, "thread::Builder", format!("{} MiB", args.stack_mib), custom_stack),
+    ];
+
+    println!("Benchmark: Recursion Depth Limit");
+    println!("Frame size: {} bytes per level", frame_bytes);
+    println!("{:<18} {:<12} {:<14}", "Thread", "Stack", "Max depth");
+    for (placement, label, stack, bound) in rows {
+        let depth = max_depth(placement, args.stack_mib, bound);
+        println!("{:<18} {:<12} {:<14}", label, stack, depth);
+    }
+}
@@ -0,0 +1,97 @@
+// String Formatting Benchmark: format! per record vs write! into one reused String
+use clap::Parser;
+use harness::{Bencher, Options};
+use std::fmt::Write;
+use std::hint::black_box;
+
+const RECORDS: usize = 1000;
+const ITERATIONS: usize = 2000;
+
+#[derive(Parser)]
+#[command(about = "format! vs write! string formatting benchmark")]
+struct Args {
+    #[command(flatten)]
+    harness: Options,
+}
+
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+struct Record {
+    id: u64,
+    name: &'static str,
+    price: f64,
+    tags: [u32; 3],
+    origin: Point,
+}
+
+const NAMES: &[&str] = &["alpha", "bravo", "charlie", "delta", "echo"];
+
+fn records() -> Vec<Record> {
+    (0..RECORDS as u64)
+        .map(|i| Record {
+            id: i * 7919,
+            name: NAMES[i as usize % NAMES.len()],
+            price: i as f64 * 1.25 + 0.99,
+            tags: [i as u32, (i * 3) as u32, (i * 5) as u32],
+            origin: Point {
+                x: i as f64 / 3.0,
+                y: -(i as f64) / 7.0,
+            },
+        })
+        .collect()
+}
+
+// One temporary String per record, appended to the output
+fn with_format(records: &[Record]) -> String {
+    let mut out = String::new();
+    for r in records {
+        out.push_str(&format!(
+            "{{id: {}, name: {:?}, price: {:.2}, tags: {:?}, origin: ({:.3}, {:.3})}}\n",
+            r.id, r.name, r.price, r.tags, r.origin.x, r.origin.y
+        ));
+    }
+    out
+}
+
+// Formats straight into the caller's buffer; after the first pass nothing allocates
+fn with_write(records: &[Record], out: &mut String) {
+    out.clear();
+    for r in records {
+        writeln!(
+            out,
+            "{{id: {}, name: {:?}, price: {:.2}, tags: {:?}, origin: ({:.3}, {:.3})}}",
+            r.id, r.name, r.price, r.tags, r.origin.x, r.origin.y
+        )
+        .unwrap();
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let records = records();
+
+    let expected = with_format(&records);
+    let mut buf = String::new();
+    with_write(&records, &mut buf);
+    assert_eq!(buf, expected, "format! and write! output differ");
+
+    let format = Bencher::new(opts, ITERATIONS).warmup(50).run(|| {
+        black_box(with_format(black_box(&records)));
+    });
+    let write = Bencher::new(opts, ITERATIONS).warmup(50).run(|| {
+        with_write(black_box(&records), &mut buf);
+        black_box(&buf);
+    });
+
+    let p = opts.precision;
+    println!("Benchmark: String formatting ({} records, {} bytes per pass)", RECORDS, expected.len());
+    println!("format! (String per call): {:.0} formats/sec", RECORDS as f64 * format.ops_per_sec());
+    println!("write! (reused String):    {:.0} formats/sec", RECORDS as f64 * write.ops_per_sec());
+    println!("Speedup: {:.p$}x", write.ops_per_sec() / format.ops_per_sec());
+    format.print_notes("format!", p);
+    write.print_notes("write!", p);
+}
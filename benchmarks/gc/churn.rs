@@ -0,0 +1,93 @@
+// GC Pressure Benchmark (illustrative): allocation churn with no collector
+// Keeps a live set of small heap objects and replaces random members in
+// batches, the allocate/free pattern that drives GC pauses in managed
+// runtimes. Rust frees each object the moment it is replaced, so there is
+// no pause to report; the point is the framing, for readers comparing it
+// with the Python and Go numbers. The slowest batch is printed alongside so
+// the allocator's own worst case is not hidden.
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 1000;
+
+#[derive(Parser)]
+#[command(about = "Allocation churn benchmark (illustrative GC-pressure framing)")]
+struct Args {
+    /// Objects kept alive at any time
+    #[arg(long, default_value_t = 100_000, value_parser = in_range(1usize, 100_000_000))]
+    live: usize,
+
+    /// Objects replaced per iteration
+    #[arg(long, default_value_t = 10_000, value_parser = in_range(1usize, 100_000_000))]
+    batch: usize,
+
+    /// Seed for which objects are replaced
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// A small object with a pointer in it, like a list node or boxed record
+struct Node {
+    id: u64,
+    payload: [u64; 3],
+    next: Option<Box<u64>>,
+}
+
+fn node(id: u64) -> Box<Node> {
+    Box::new(Node {
+        id,
+        payload: [id; 3],
+        next: Some(Box::new(id)),
+    })
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let mut live: Vec<Box<Node>> = (0..args.live as u64).map(node).collect();
+    let mut rng = XorShift64::new(args.seed);
+    let mut next_id = args.live as u64;
+
+    let m = Bencher::new(opts, ITERATIONS).warmup(10).run(|| {
+        for _ in 0..args.batch {
+            let slot = rng.below(live.len() as u64) as usize;
+            // The replaced node and its child are freed right here
+            live[slot] = black_box(node(next_id));
+            next_id += 1;
+        }
+    });
+
+    // Every replacement kept the live set's shape
+    assert_eq!(live.len(), args.live, "Live set changed size");
+    assert!(
+        live.iter().all(|n| n.payload == [n.id; 3] && n.next.as_deref() == Some(&n.id)),
+        "Live object corrupted"
+    );
+
+    let p = opts.precision;
+    // Each replacement allocates a Node and its child Box
+    let allocations_per_sec = 2.0 * args.batch as f64 * m.ops_per_sec();
+    let bytes_per_alloc = (std::mem::size_of::<Node>() + std::mem::size_of::<u64>()) as f64 / 2.0;
+    let slowest_batch = m.samples.iter().flatten().max().copied().unwrap_or_default();
+
+    println!("Benchmark: Allocation churn (illustrative GC-pressure framing)");
+    println!("Live objects: {}, replaced per iteration: {}", args.live, args.batch);
+    println!("Iterations: {}", ITERATIONS);
+    println!(
+        "Allocation rate: {:.0} allocs/sec ({:.p$} MB/s)",
+        allocations_per_sec,
+        allocations_per_sec * bytes_per_alloc / 1e6
+    );
+    println!("Rust pause: 0ms (no GC)");
+    println!(
+        "Slowest batch: {:.p$}µs for {} replacements (allocator and scheduling, not a collection)",
+        slowest_batch.as_secs_f64() * 1_000_000.0,
+        args.batch
+    );
+    m.print_notes("Allocation rate", p);
+}
@@ -0,0 +1,149 @@
+// Monomorphization Benchmark: generic &[T: Num] vs an erased &dyn interface
+// Computes the same dot product over --n elements twice per element type:
+// once as dot::<T>, which the compiler instantiates per type with add and
+// mul inlined into the loop, and once as dot_dyn, which reaches the same
+// arithmetic through a &dyn NumOps<T> vtable, one call per operation. The
+// erased form is dictionary passing, how generics work without
+// monomorphization (Go's shape-based generics, type classes). Unlike the
+// closure dispatch benchmark, every element pays for two trait calls in the
+// loop.
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 200;
+
+#[derive(Parser)]
+#[command(about = "Monomorphized generic vs type-erased trait arithmetic benchmark")]
+struct Args {
+    /// Elements in each vector
+    #[arg(long, default_value_t = 100_000, value_parser = in_range(1usize, 100_000_000))]
+    n: usize,
+
+    /// Seed for the vector contents
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+trait Num: Copy + PartialEq + std::fmt::Debug {
+    fn zero() -> Self;
+    fn add(self, other: Self) -> Self;
+    fn mul(self, other: Self) -> Self;
+}
+
+impl Num for i64 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn add(self, other: Self) -> Self {
+        self.wrapping_add(other)
+    }
+
+    fn mul(self, other: Self) -> Self {
+        self.wrapping_mul(other)
+    }
+}
+
+impl Num for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn mul(self, other: Self) -> Self {
+        self * other
+    }
+}
+
+// Num with the type erased into a vtable: callers hold a &dyn NumOps<T>
+// and cannot see which impl is behind it
+trait NumOps<T> {
+    fn zero(&self) -> T;
+    fn add(&self, a: T, b: T) -> T;
+    fn mul(&self, a: T, b: T) -> T;
+}
+
+struct Ops;
+
+impl<T: Num> NumOps<T> for Ops {
+    fn zero(&self) -> T {
+        T::zero()
+    }
+
+    fn add(&self, a: T, b: T) -> T {
+        a.add(b)
+    }
+
+    fn mul(&self, a: T, b: T) -> T {
+        a.mul(b)
+    }
+}
+
+fn dot<T: Num>(a: &[T], b: &[T]) -> T {
+    a.iter().zip(b).fold(T::zero(), |acc, (&x, &y)| acc.add(x.mul(y)))
+}
+
+fn dot_dyn<T: Copy>(ops: &dyn NumOps<T>, a: &[T], b: &[T]) -> T {
+    a.iter().zip(b).fold(ops.zero(), |acc, (&x, &y)| ops.add(acc, ops.mul(x, y)))
+}
+
+struct Row {
+    name: &'static str,
+    mono: Measurement,
+    erased: Measurement,
+}
+
+fn measure<T: Num>(opts: &Options, name: &'static str, a: &[T], b: &[T]) -> Row {
+    // black_box on the trait object keeps the optimizer from devirtualizing
+    let ops: &dyn NumOps<T> = black_box(&Ops);
+    // Same operations in the same order, so even f64 totals match exactly
+    assert_eq!(dot_dyn(ops, a, b), dot(a, b), "{} erased and generic dot products differ", name);
+
+    let mono = Bencher::new(opts, ITERATIONS).warmup(10).run(|| {
+        black_box(dot(black_box(a), black_box(b)));
+    });
+    let erased = Bencher::new(opts, ITERATIONS).warmup(10).run(|| {
+        black_box(dot_dyn(black_box(ops), black_box(a), black_box(b)));
+    });
+    Row { name, mono, erased }
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+
+    let mut rng = XorShift64::new(args.seed);
+    let ints: Vec<i64> = (0..args.n * 2).map(|_| rng.below(1000) as i64).collect();
+    let floats: Vec<f64> = (0..args.n * 2).map(|_| rng.next_f64()).collect();
+    let (ia, ib) = ints.split_at(args.n);
+    let (fa, fb) = floats.split_at(args.n);
+
+    let rows = [measure(opts, "i64", ia, ib), measure(opts, "f64", fa, fb)];
+
+    let p = opts.precision;
+    let ns = |m: &Measurement| m.per_iter().as_secs_f64() * 1e9 / args.n as f64;
+    println!("Benchmark: Monomorphized vs type-erased dot product ({} elements)", args.n);
+    println!("Iterations: {}", ITERATIONS);
+    println!("{:<6} {:<20} {:<20} {:<10}", "Type", "Generic (ns/elem)", "&dyn (ns/elem)", "Overhead");
+    for row in &rows {
+        println!(
+            "{:<6} {:<20.p$} {:<20.p$} {:.p$}x",
+            row.name,
+            ns(&row.mono),
+            ns(&row.erased),
+            ns(&row.erased) / ns(&row.mono)
+        );
+    }
+    for row in &rows {
+        row.mono.print_notes(&format!("{} generic", row.name), p);
+        row.erased.print_notes(&format!("{} &dyn", row.name), p);
+    }
+}
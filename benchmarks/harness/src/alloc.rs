@@ -0,0 +1,26 @@
+//! Global allocator selection. Building with `--features jemalloc` (forwarded
+//! by every benchmark crate to the harness) swaps the system allocator for
+//! jemalloc in the whole binary, so allocation-heavy benchmarks can be run
+//! with the allocator a production binary would use.
+
+use std::sync::Once;
+
+#[cfg(feature = "jemalloc")]
+#[global_allocator]
+static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+
+/// The global allocator this binary was built with: `"jemalloc"` or `"system"`.
+pub fn allocator() -> &'static str {
+    if cfg!(feature = "jemalloc") {
+        "jemalloc"
+    } else {
+        "system"
+    }
+}
+
+/// Prints the allocator line to stderr, at most once per process. It goes
+/// to stderr so table and report output stay as they were.
+pub(crate) fn report_allocator() {
+    static REPORTED: Once = Once::new();
+    REPORTED.call_once(|| eprintln!("Allocator: {}", allocator()));
+}
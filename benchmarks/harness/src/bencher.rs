@@ -0,0 +1,416 @@
+use std::hint::black_box;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::alloc::report_allocator;
+use crate::priority;
+use crate::stats::percentile;
+use crate::{check_build_profile, Options};
+
+/// Runs a workload through warmup and one or more measured loops.
+pub struct Bencher<'a> {
+    opts: &'a Options,
+    warmup: usize,
+    steady_state_warmup: usize,
+    iterations: usize,
+}
+
+/// Timings collected by [`Bencher::run`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Measurement {
+    /// Wall time of each repeat's measured loop.
+    pub repeats: Vec<Duration>,
+    /// Per-iteration timings, one vector per repeat.
+    pub samples: Vec<Vec<Duration>>,
+    /// `Some` under `--repeat-until-stable`: whether the running mean settled
+    /// before `--max-repeats` was reached.
+    pub converged: Option<bool>,
+    /// First measured iteration under `--no-warmup`, i.e. the cold-start cost.
+    pub cold: Option<Duration>,
+    /// Per-loop time budget under `--duration`.
+    pub budget: Option<Duration>,
+    /// Iterations the `--warmup-time` budget took to run out.
+    pub warmup_iterations: Option<usize>,
+    /// `--target-rate` under `--coordinated-omission-safe`.
+    #[serde(default)]
+    pub target_rate: Option<f64>,
+    /// Under `--coordinated-omission-safe`, each iteration's latency from its
+    /// scheduled start to its end, across all repeats.
+    #[serde(default)]
+    pub latencies: Vec<Duration>,
+    /// Under `--measure-overhead`, the harness's own per-iteration cost that
+    /// was subtracted from every sample and loop time.
+    #[serde(default)]
+    pub overhead: Option<Duration>,
+}
+
+impl<'a> Bencher<'a> {
+    pub fn new(opts: &'a Options, iterations: usize) -> Self {
+        // Nothing is timed under --check-only, so a debug build is fine
+        if !opts.check_only {
+            check_build_profile(opts.allow_debug);
+        }
+        // An --isolate child's stderr is echoed by the parent, which has
+        // already said it
+        if opts.isolated_child.is_none() {
+            report_allocator();
+        }
+        Self {
+            opts,
+            warmup: 0,
+            steady_state_warmup: 0,
+            iterations,
+        }
+    }
+
+    /// Untimed iterations run once before the first measured loop.
+    pub fn warmup(mut self, iterations: usize) -> Self {
+        self.warmup = iterations;
+        self
+    }
+
+    /// Warmup used instead of [`Bencher::warmup`] under `--steady-state`, sized
+    /// so the allocator and any buffers the workload grows are already at
+    /// their high-water mark when measurement starts.
+    pub fn steady_state_warmup(mut self, iterations: usize) -> Self {
+        self.steady_state_warmup = iterations;
+        self
+    }
+
+    pub fn run<F: FnMut()>(self, mut f: F) -> Measurement {
+        self.measure(false, || {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+    }
+
+    /// Like [`Bencher::run`], but builds each iteration's input with `setup`
+    /// outside the timed region, so only `f` is measured; a loop's time is
+    /// then the sum of its iterations. `--duration` budgets still count the
+    /// setup time.
+    pub fn run_with_setup<I, S, F>(self, mut setup: S, mut f: F) -> Measurement
+    where
+        S: FnMut() -> I,
+        F: FnMut(I),
+    {
+        self.measure(true, || {
+            let input = setup();
+            let start = Instant::now();
+            f(input);
+            start.elapsed()
+        })
+    }
+
+    // `iteration` runs the workload once and returns the time to count for it
+    fn measure(self, setup_excluded: bool, mut iteration: impl FnMut() -> Duration) -> Measurement {
+        // --check-only: the workload's own checks are the point, not its time
+        if self.opts.check_only {
+            let elapsed = iteration();
+            return Measurement {
+                repeats: vec![elapsed],
+                samples: vec![vec![elapsed]],
+                converged: None,
+                cold: None,
+                budget: None,
+                warmup_iterations: None,
+                target_rate: None,
+                latencies: Vec::new(),
+                overhead: None,
+            };
+        }
+        // Held until every repeat is done; dropping it restores the priority
+        let _raised = priority::raise(self.opts.priority);
+        let warmup = match (self.opts.no_warmup, self.opts.steady_state) {
+            (true, _) => 0,
+            (false, true) => self.warmup.max(self.steady_state_warmup),
+            (false, false) => self.warmup,
+        };
+        let mut warmup_iterations = None;
+        if let Some(warmup_time) = self.opts.warmup_time {
+            let start = Instant::now();
+            let mut n = 0;
+            while start.elapsed() < warmup_time {
+                iteration();
+                n += 1;
+            }
+            warmup_iterations = Some(n);
+        } else {
+            for _ in 0..warmup {
+                iteration();
+            }
+        }
+
+        // Each measured loop runs a fixed iteration count, or under --duration
+        // as many iterations as fit in the time budget
+        let budget = self.opts.duration;
+        // Under --coordinated-omission-safe iteration n of a loop is due at
+        // n / rate after the loop starts. Its latency counts from then, so an
+        // iteration that stalls also charges the ones queued up behind it.
+        let interval = self.opts.target_rate.filter(|_| self.opts.coordinated_omission_safe).map(|rate| 1.0 / rate);
+        let overhead = self.opts.measure_overhead.then(overhead);
+        let mut samples = Vec::new();
+        let mut latencies = Vec::new();
+        let mut measure = || {
+            let mut repeat_samples = Vec::with_capacity(if budget.is_some() { 0 } else { self.iterations });
+            let start = Instant::now();
+            loop {
+                let done = match budget {
+                    Some(budget) => start.elapsed() >= budget,
+                    None => repeat_samples.len() >= self.iterations,
+                };
+                if done {
+                    break;
+                }
+                match interval {
+                    Some(interval) => {
+                        let due = start + Duration::from_secs_f64(interval * repeat_samples.len() as f64);
+                        wait_until(due);
+                        repeat_samples.push(iteration());
+                        latencies.push(due.elapsed());
+                    }
+                    None => repeat_samples.push(iteration()),
+                }
+            }
+            let mut elapsed = start.elapsed();
+            if let Some(overhead) = overhead {
+                for sample in &mut repeat_samples {
+                    *sample = sample.saturating_sub(overhead.per_sample);
+                }
+                elapsed = elapsed.saturating_sub(overhead.per_iter * repeat_samples.len() as u32);
+            }
+            // Paced loops spend most of their wall time waiting for the schedule
+            if setup_excluded || interval.is_some() {
+                elapsed = repeat_samples.iter().sum();
+            }
+            let per_iter = elapsed.as_secs_f64() / repeat_samples.len().max(1) as f64;
+            samples.push(repeat_samples);
+            (elapsed, per_iter)
+        };
+
+        let mut repeats = Vec::new();
+        let mut converged = None;
+        if self.opts.repeat_until_stable {
+            // Convergence tracks the running mean of per-iteration time, which
+            // stays meaningful when --duration fixes each loop's wall time
+            let threshold = self.opts.stable_threshold / 100.0;
+            let mut per_iters = Vec::new();
+            let mut prev_mean: Option<f64> = None;
+            converged = Some(false);
+            while repeats.len() < self.opts.max_repeats.max(1) {
+                let (elapsed, per_iter) = measure();
+                repeats.push(elapsed);
+                per_iters.push(per_iter);
+                let mean = per_iters.iter().sum::<f64>() / per_iters.len() as f64;
+                if let Some(prev) = prev_mean {
+                    if ((mean - prev) / prev).abs() < threshold {
+                        converged = Some(true);
+                        break;
+                    }
+                }
+                prev_mean = Some(mean);
+            }
+        } else {
+            for _ in 0..self.opts.repeat.max(1) {
+                repeats.push(measure().0);
+            }
+        }
+
+        let cold = if self.opts.no_warmup {
+            samples.first().and_then(|s| s.first()).copied()
+        } else {
+            None
+        };
+
+        Measurement {
+            repeats,
+            samples,
+            converged,
+            cold,
+            budget,
+            warmup_iterations,
+            target_rate: interval.map(|interval| 1.0 / interval),
+            latencies,
+            overhead: overhead.map(|overhead| overhead.per_iter),
+        }
+    }
+}
+
+// What the harness itself adds to one iteration of an empty workload: the
+// loop's wall time per iteration, and the time one timed sample reads
+#[derive(Clone, Copy)]
+struct Overhead {
+    per_iter: Duration,
+    per_sample: Duration,
+}
+
+// Calibrated on first use and reported to stderr once per process
+fn overhead() -> Overhead {
+    static OVERHEAD: OnceLock<Overhead> = OnceLock::new();
+    *OVERHEAD.get_or_init(|| {
+        let overhead = calibrate();
+        eprintln!(
+            "Harness overhead: {} ns per iteration ({} ns per timed sample), subtracted from results",
+            overhead.per_iter.as_nanos(),
+            overhead.per_sample.as_nanos()
+        );
+        overhead
+    })
+}
+
+// Runs an empty workload through the same loop shape `Bencher::run` uses and
+// keeps the lowest of a few rounds, since noise only ever adds time
+fn calibrate() -> Overhead {
+    const ROUNDS: usize = 5;
+    const ITERATIONS: usize = 100_000;
+    let mut per_iter = Duration::MAX;
+    let mut per_sample = Duration::MAX;
+    for _ in 0..ROUNDS {
+        let mut samples = Vec::with_capacity(ITERATIONS);
+        let start = Instant::now();
+        while samples.len() < ITERATIONS {
+            let sample_start = Instant::now();
+            black_box(());
+            samples.push(sample_start.elapsed());
+        }
+        per_iter = per_iter.min(start.elapsed() / ITERATIONS as u32);
+        samples.sort();
+        per_sample = per_sample.min(samples[ITERATIONS / 2]);
+    }
+    Overhead { per_iter, per_sample }
+}
+
+// Sleeps most of the way to `due`, then spins, since a sleep can overshoot
+// by tens of microseconds
+fn wait_until(due: Instant) {
+    const SPIN: Duration = Duration::from_micros(200);
+    let now = Instant::now();
+    if due > now + SPIN {
+        thread::sleep(due - now - SPIN);
+    }
+    while Instant::now() < due {
+        std::hint::spin_loop();
+    }
+}
+
+impl Measurement {
+    /// Appends another run of the same benchmark (a later `--suite-repeats`
+    /// pass) as further repeats. Cold-start and warmup notes stay from the
+    /// first run; convergence is only known per run, so it is dropped.
+    pub(crate) fn absorb(&mut self, other: Measurement) {
+        self.repeats.extend(other.repeats);
+        self.samples.extend(other.samples);
+        self.latencies.extend(other.latencies);
+        self.converged = None;
+    }
+
+    /// Mean wall time of one measured loop across repeats.
+    pub fn total(&self) -> Duration {
+        Duration::from_secs_f64(mean_secs(&self.repeats))
+    }
+
+    /// Mean number of iterations per measured loop.
+    pub fn iterations(&self) -> usize {
+        self.sample_count() / self.repeats.len().max(1)
+    }
+
+    /// Mean wall time of a single iteration.
+    pub fn per_iter(&self) -> Duration {
+        let total: Duration = self.repeats.iter().sum();
+        total / self.sample_count().max(1) as u32
+    }
+
+    /// Iterations completed per second of measured wall time.
+    pub fn ops_per_sec(&self) -> f64 {
+        let total: Duration = self.repeats.iter().sum();
+        self.sample_count() as f64 / total.as_secs_f64()
+    }
+
+    fn sample_count(&self) -> usize {
+        self.samples.iter().map(Vec::len).sum()
+    }
+
+    /// Short annotations for a result row: operations completed under a time
+    /// budget, how the repeat count was reached, the cold-start time (printed
+    /// with `p` decimal places), iterations spent in a timed warmup and any
+    /// subtracted harness overhead.
+    pub fn notes(&self, p: usize) -> Vec<String> {
+        let mut notes = Vec::new();
+        if let Some(budget) = self.budget {
+            notes.push(format!("{} ops in {:?}", self.iterations(), budget));
+        }
+        match self.converged {
+            Some(true) => notes.push(format!("stable after {} repeats", self.repeats.len())),
+            Some(false) => notes.push(format!("unstable after {} repeats", self.repeats.len())),
+            None if self.repeats.len() > 1 => notes.push(format!("mean of {} repeats", self.repeats.len())),
+            None => {}
+        }
+        if let Some(cold) = self.cold {
+            notes.push(format!("cold: {:.p$} µs", cold.as_secs_f64() * 1_000_000.0));
+        }
+        if let Some(n) = self.warmup_iterations {
+            notes.push(format!("warmup: {} iters", n));
+        }
+        if let Some(overhead) = self.overhead {
+            notes.push(format!("overhead -{} ns/iter", overhead.as_nanos()));
+        }
+        if let Some(p99) = self.corrected_latency(99.0) {
+            notes.push(format!("corrected p99: {:.p$} µs", p99.as_secs_f64() * 1_000_000.0));
+        }
+        notes
+    }
+
+    /// Prints `label: notes` when [`Measurement::notes`] has any, so binaries
+    /// that print their own result lines still report what the harness flags
+    /// did (repeats to convergence, timed warmup, ...). Prints nothing for a
+    /// plain run.
+    pub fn print_notes(&self, label: &str, p: usize) {
+        let notes = self.notes(p);
+        if !notes.is_empty() {
+            println!("{}: {}", label, notes.join(", "));
+        }
+    }
+
+    /// Percentile `p` of the coordinated-omission-corrected latencies, or
+    /// `None` outside `--coordinated-omission-safe`.
+    pub fn corrected_latency(&self, p: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let mut sorted = self.latencies.clone();
+        sorted.sort();
+        Some(percentile(&sorted, p))
+    }
+
+    /// One line summarizing the corrected latencies (p50, p99, p99.9 and max
+    /// with `p` decimal places) and whether the target rate was kept up, or
+    /// `None` outside `--coordinated-omission-safe`.
+    pub fn latency_summary(&self, p: usize) -> Option<String> {
+        let rate = self.target_rate?;
+        let us = |percent| self.corrected_latency(percent).unwrap_or_default().as_secs_f64() * 1_000_000.0;
+        let mut line = format!(
+            "corrected latency at {} ops/s: p50 {:.p$} µs, p99 {:.p$} µs, p99.9 {:.p$} µs, max {:.p$} µs",
+            rate,
+            us(50.0),
+            us(99.0),
+            us(99.9),
+            us(100.0)
+        );
+        // Busy for more than the whole schedule, the backlog only grows and
+        // the latencies describe it rather than the workload
+        let utilization = rate / self.ops_per_sec() * 100.0;
+        if utilization < 100.0 {
+            line.push_str(&format!(" (utilization {:.p$}%)", utilization));
+        } else {
+            line.push_str(&format!(" (target rate not sustained: at most {:.0} ops/s)", self.ops_per_sec()));
+        }
+        Some(line)
+    }
+}
+
+fn mean_secs(durations: &[Duration]) -> f64 {
+    durations.iter().map(Duration::as_secs_f64).sum::<f64>() / durations.len().max(1) as f64
+}
@@ -0,0 +1,96 @@
+//! `--color`: marks the slowest row of the live table and dims the fastest.
+//!
+//! Which rows those are is only known once every benchmark has finished, so
+//! while color is on the rows given to [`print_row`] are held per benchmark
+//! and printed together at the end of [`crate::Suite::run`]. With color off
+//! they print immediately, as the rows stream in.
+
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::{Mutex, PoisonError};
+
+use clap::ValueEnum;
+
+use crate::{progress, BenchmarkResult, Format, SuiteOptions};
+
+const SLOWEST: &str = "\x1b[1;31m";
+const FASTEST: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// When `--color` applies.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Only when stdout is a terminal and `NO_COLOR` is unset
+    Auto,
+    Always,
+    Never,
+}
+
+// Rows printed by the running benchmark, while color is on
+static HELD: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+/// Prints one table row for the running benchmark, or holds it for coloring
+/// once the suite finishes. Benchmarks run through a [`crate::Suite`] print
+/// their rows with this rather than `println!`.
+pub fn print_row(line: &str) {
+    match HELD.lock().unwrap_or_else(PoisonError::into_inner).as_mut() {
+        Some(rows) => rows.push(line.to_string()),
+        None => progress::suspend(|| println!("{}", line)),
+    }
+}
+
+/// Whether this run colors its table. Reports in other formats, and
+/// `--isolate` children whose output the parent collects, are never colored.
+pub(crate) fn enabled(opts: &SuiteOptions) -> bool {
+    if opts.format != Format::Table || opts.isolated_child.is_some() {
+        return false;
+    }
+    match opts.color {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+    }
+}
+
+/// Starts holding rows for the next benchmark.
+pub(crate) fn hold() {
+    *HELD.lock().unwrap_or_else(PoisonError::into_inner) = Some(Vec::new());
+}
+
+/// Stops holding and returns the rows the benchmark printed.
+pub(crate) fn release() -> Vec<String> {
+    HELD.lock().unwrap_or_else(PoisonError::into_inner).take().unwrap_or_default()
+}
+
+/// Prints each benchmark's held rows, `rows[i]` belonging to `results[i]`,
+/// with the slowest and fastest successful results marked. Nothing is marked
+/// with fewer than two successes.
+pub(crate) fn print_held(rows: &[Vec<String>], results: &[BenchmarkResult]) {
+    let mut ranked: Vec<(usize, f64)> = results
+        .iter()
+        .enumerate()
+        .filter_map(|(i, r)| r.outcome.as_ref().ok().map(|m| (i, m.per_iter().as_secs_f64())))
+        .collect();
+    ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let (fastest, slowest) = match (ranked.first(), ranked.last()) {
+        (Some(fastest), Some(slowest)) if ranked.len() > 1 => (Some(fastest.0), Some(slowest.0)),
+        _ => (None, None),
+    };
+
+    for (i, lines) in rows.iter().enumerate() {
+        let style = if Some(i) == slowest {
+            SLOWEST
+        } else if Some(i) == fastest {
+            FASTEST
+        } else {
+            ""
+        };
+        for line in lines {
+            if style.is_empty() {
+                println!("{}", line);
+            } else {
+                println!("{}{}{}", style, line, RESET);
+            }
+        }
+    }
+}
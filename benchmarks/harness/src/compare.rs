@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::process::Command;
+
+/// One sibling-language run for `--compare-langs`: per-benchmark values
+/// parsed from its table, or why it could not be run.
+pub struct Sibling {
+    pub lang: String,
+    pub values: Result<HashMap<String, f64>, String>,
+}
+
+impl Sibling {
+    /// Runs `command` (program and arguments, split on whitespace) and parses
+    /// its result table. A missing program or failed run is recorded, not fatal.
+    ///
+    /// The siblings print the same fixed-width table as the Rust binaries: the
+    /// benchmark name padded to `name_width` columns, then whitespace-separated
+    /// fields, of which `column` (0-based, after the name) is read.
+    pub fn run(lang: &str, command: &str, name_width: usize, column: usize) -> Self {
+        let values = run_command(command).map(|out| parse_rows(&out, name_width, column));
+        Self {
+            lang: lang.to_string(),
+            values,
+        }
+    }
+}
+
+fn run_command(command: &str) -> Result<String, String> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().ok_or("empty command")?;
+    let output = Command::new(program)
+        .args(parts)
+        .output()
+        .map_err(|e| format!("`{}`: {}", command, e))?;
+    if !output.status.success() {
+        return Err(format!("`{}` exited with {}", command, output.status));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn parse_rows(output: &str, name_width: usize, column: usize) -> HashMap<String, f64> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let split = line.char_indices().nth(name_width).map(|(i, _)| i)?;
+            let (name, rest) = line.split_at(split);
+            let value = rest.split_whitespace().nth(column)?.parse().ok()?;
+            Some((name.trim().to_string(), value))
+        })
+        .collect()
+}
+
+/// Prints the Rust value next to each sibling's, with Rust's speedup over
+/// each, to `p` decimal places. Lower values are taken to be faster (times,
+/// not rates).
+pub fn print_comparison(unit: &str, p: usize, rust: &[(String, f64)], siblings: &[Sibling]) {
+    let mut header = format!("{:<20} {:<12}", "Benchmark", format!("Rust ({})", unit));
+    for sibling in siblings {
+        header.push_str(&format!(" {:<14}", format!("{} ({})", sibling.lang, unit)));
+    }
+    for sibling in siblings {
+        header.push_str(&format!(" {:<10}", format!("vs {}", sibling.lang)));
+    }
+    println!("{}", header);
+    println!("{}", "-".repeat(header.len().max(70)));
+
+    for (name, value) in rust {
+        let others: Vec<Option<f64>> = siblings
+            .iter()
+            .map(|s| s.values.as_ref().ok().and_then(|v| v.get(name)).copied())
+            .collect();
+        let mut row = format!("{:<20} {:<12.p$}", name, value);
+        for other in &others {
+            match other {
+                Some(other) => row.push_str(&format!(" {:<14.p$}", other)),
+                None => row.push_str(&format!(" {:<14}", "-")),
+            }
+        }
+        for other in &others {
+            match other {
+                Some(other) => row.push_str(&format!(" {:<10}", format!("{:.p$}x", other / value))),
+                None => row.push_str(&format!(" {:<10}", "-")),
+            }
+        }
+        println!("{}", row);
+    }
+
+    for sibling in siblings {
+        if let Err(e) = &sibling.values {
+            println!("{}: not available ({})", sibling.lang, e);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::Options;
+
+/// A text corpus read from disk once per process and shared between
+/// benchmarks, repeats and iterations.
+#[derive(Debug, Clone)]
+pub struct Corpus {
+    path: PathBuf,
+    text: Arc<str>,
+}
+
+impl Corpus {
+    /// Loads `path`, returning the cached copy if it was loaded before.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<str>>>> = OnceLock::new();
+        let path = path.as_ref().to_path_buf();
+        let mut cache = CACHE.get_or_init(Default::default).lock().unwrap();
+        let text = match cache.get(&path) {
+            Some(text) => Arc::clone(text),
+            None => {
+                let text: Arc<str> = fs::read_to_string(&path)?.into();
+                cache.insert(path.clone(), Arc::clone(&text));
+                text
+            }
+        };
+        Ok(Self { path, text })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The corpus for one measured iteration: the cached text, or a fresh
+    /// read from disk under `--include-io`.
+    pub fn fetch(&self, opts: &Options) -> Arc<str> {
+        if opts.include_io {
+            fs::read_to_string(&self.path)
+                .expect("Failed to re-read corpus")
+                .into()
+        } else {
+            Arc::clone(&self.text)
+        }
+    }
+}
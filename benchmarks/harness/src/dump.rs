@@ -0,0 +1,34 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::{Measurement, SuiteOptions};
+
+/// Raw sample sink behind `--dump-samples`.
+///
+/// Writes `benchmark,repeat,iteration,ns` rows so the samples can be loaded
+/// straight into pandas or R.
+pub struct SampleDump {
+    out: BufWriter<File>,
+}
+
+impl SampleDump {
+    /// Opens the dump file and writes the header, or returns `None` when the
+    /// flag was not given.
+    pub fn create(opts: &SuiteOptions) -> io::Result<Option<Self>> {
+        let Some(path) = &opts.dump_samples else {
+            return Ok(None);
+        };
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "benchmark,repeat,iteration,ns")?;
+        Ok(Some(Self { out }))
+    }
+
+    pub fn write(&mut self, name: &str, measurement: &Measurement) -> io::Result<()> {
+        for (repeat, samples) in measurement.samples.iter().enumerate() {
+            for (iteration, sample) in samples.iter().enumerate() {
+                writeln!(self.out, "{},{},{},{}", name, repeat, iteration, sample.as_nanos())?;
+            }
+        }
+        self.out.flush()
+    }
+}
@@ -0,0 +1,19 @@
+use clap::ValueEnum;
+
+/// Output format for benchmark results.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Fixed-width text table
+    #[default]
+    Table,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Prometheus text exposition format, one gauge per metric
+    Prometheus,
+    /// One JSON object per result, each on its own line as it finishes
+    Jsonl,
+    /// Self-contained HTML page with an inline SVG bar chart
+    Html,
+    /// Bencher Metric Format JSON, for bencher.dev and similar trackers
+    Bencher,
+}
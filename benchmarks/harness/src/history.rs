@@ -0,0 +1,173 @@
+//! `--sqlite PATH`: appends every run to a SQLite database so results can be
+//! queried as a performance history.
+//!
+//! Each run is one row in `runs` (when, which binary, the git commit and the
+//! `--tag`s as a JSON object) and one row per benchmark in `results`, with
+//! the same columns as the CSV report. The schema is created if the file is
+//! new. A run is written in a single transaction once the suite finishes, and
+//! concurrent writers wait on the database lock instead of failing.
+//!
+//! `--set-baseline` also lists the run in `baselines`. Without a `--baseline`
+//! file, the latest baseline recorded for the same binary is loaded when the
+//! database is opened and compared against.
+
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::report::unique_tags;
+use crate::{spread_percent, BenchmarkResult, Failure, Measurement, Quality, SuiteOptions};
+
+// How long a writer waits for another process's transaction to finish
+const BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    started_at INTEGER NOT NULL,
+    binary TEXT NOT NULL,
+    git_commit TEXT,
+    tags TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS results (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    category TEXT NOT NULL,
+    benchmark TEXT NOT NULL,
+    status TEXT NOT NULL,
+    iterations INTEGER,
+    avg_ns REAL,
+    ops_per_sec REAL,
+    spread_percent REAL,
+    quality TEXT,
+    error TEXT
+);
+CREATE INDEX IF NOT EXISTS results_benchmark ON results (benchmark, run_id);
+CREATE TABLE IF NOT EXISTS baselines (
+    run_id INTEGER PRIMARY KEY REFERENCES runs(id),
+    set_at INTEGER NOT NULL
+);
+";
+
+pub(crate) struct History {
+    conn: Connection,
+    /// Unix seconds when the suite started.
+    started_at: u64,
+}
+
+/// A run marked with `--set-baseline`.
+pub(crate) struct Baseline {
+    pub(crate) run_id: i64,
+    pub(crate) git_commit: Option<String>,
+    /// Mean ns per iteration of its successful results, by benchmark name.
+    pub(crate) avg_ns: HashMap<String, f64>,
+}
+
+impl History {
+    /// Opens or creates the database and its schema, before anything runs so
+    /// a bad path fails fast.
+    pub(crate) fn open(path: &Path) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.execute_batch(SCHEMA)?;
+        let started_at = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        Ok(Self { conn, started_at })
+    }
+
+    /// The most recently set baseline for this binary, if there is one.
+    pub(crate) fn latest_baseline(&self) -> rusqlite::Result<Option<Baseline>> {
+        let run = self
+            .conn
+            .query_row(
+                "SELECT b.run_id, r.git_commit FROM baselines b JOIN runs r ON r.id = b.run_id
+                 WHERE r.binary = ?1 ORDER BY b.set_at DESC, b.run_id DESC LIMIT 1",
+                params![binary_name()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((run_id, git_commit)) = run else {
+            return Ok(None);
+        };
+        let mut select = self
+            .conn
+            .prepare("SELECT benchmark, avg_ns FROM results WHERE run_id = ?1 AND status = 'ok'")?;
+        let avg_ns = select
+            .query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(Some(Baseline {
+            run_id,
+            git_commit,
+            avg_ns,
+        }))
+    }
+
+    /// Writes the run and all of its results in one transaction, marking it
+    /// as the baseline under `--set-baseline`.
+    pub(crate) fn record(mut self, opts: &SuiteOptions, results: &[BenchmarkResult]) -> rusqlite::Result<()> {
+        let tags: BTreeMap<String, String> = unique_tags(&opts.tags).into_iter().collect();
+        let tags = serde_json::to_string(&tags).expect("tags serialize");
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO runs (started_at, binary, git_commit, tags) VALUES (?1, ?2, ?3, ?4)",
+            params![self.started_at as i64, binary_name(), git_commit(), tags],
+        )?;
+        let run_id = tx.last_insert_rowid();
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO results (run_id, category, benchmark, status, iterations, avg_ns, ops_per_sec,
+                 spread_percent, quality, error) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+            for r in results {
+                let ok = r.outcome.as_ref().ok();
+                let (status, error) = match &r.outcome {
+                    Ok(_) => ("ok", None),
+                    Err(Failure::Error(message)) => ("error", Some(message.as_str())),
+                    Err(Failure::Panic(message)) => ("panic", Some(message.as_str())),
+                };
+                insert.execute(params![
+                    run_id,
+                    r.category,
+                    r.name,
+                    status,
+                    ok.map(|m| m.iterations() as i64),
+                    ok.map(|m| m.per_iter().as_secs_f64() * 1e9),
+                    ok.map(Measurement::ops_per_sec),
+                    ok.map(spread_percent),
+                    r.quality.map(Quality::label),
+                    error,
+                ])?;
+            }
+        }
+        if opts.set_baseline {
+            let set_at = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+            tx.execute(
+                "INSERT INTO baselines (run_id, set_at) VALUES (?1, ?2)",
+                params![run_id, set_at as i64],
+            )?;
+        }
+        tx.commit()?;
+        if opts.set_baseline {
+            eprintln!("Baseline: run {} is now the baseline for {}", run_id, binary_name());
+        }
+        Ok(())
+    }
+}
+
+fn binary_name() -> String {
+    env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .unwrap_or_default()
+}
+
+// HEAD of the working directory's repository, if there is one
+fn git_commit() -> Option<String> {
+    let output = Command::new("git").args(["rev-parse", "HEAD"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
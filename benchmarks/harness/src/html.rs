@@ -0,0 +1,136 @@
+//! `--format html`: one self-contained file with the results as an inline
+//! SVG bar chart and a table. No scripts and no external resources, so it
+//! can be attached to an issue or mailed as-is.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::suite::by_category;
+use crate::{BenchmarkResult, Quality};
+
+const BAR_WIDTH: f64 = 480.0;
+const ROW_HEIGHT: f64 = 26.0;
+const LABEL_WIDTH: f64 = 180.0;
+
+/// Writes the report. With a baseline (benchmark name to mean ns per
+/// iteration), each bar gets a thinner baseline bar beneath it and the table
+/// shows the change.
+pub(crate) fn write_html(
+    out: &mut dyn Write,
+    results: &[BenchmarkResult],
+    tags: &[(String, String)],
+    baseline: Option<&HashMap<String, f64>>,
+) -> io::Result<()> {
+    let rows: Vec<(&BenchmarkResult, Option<f64>)> = by_category(results)
+        .into_iter()
+        .flat_map(|(_, group)| group)
+        .map(|r| (r, r.outcome.as_ref().ok().map(|m| m.per_iter().as_secs_f64() * 1e9)))
+        .collect();
+    let max_ns = rows
+        .iter()
+        .flat_map(|(r, ns)| [*ns, baseline.and_then(|b| b.get(&r.name).copied())])
+        .flatten()
+        .fold(0.0, f64::max)
+        .max(f64::MIN_POSITIVE);
+
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html><head><meta charset=\"utf-8\"><title>metal0 benchmark report</title>")?;
+    writeln!(
+        out,
+        "<style>body{{font:14px sans-serif;margin:2em}}table{{border-collapse:collapse}}\
+         td,th{{padding:4px 10px;border-bottom:1px solid #ddd;text-align:right}}\
+         td:first-child,th:first-child{{text-align:left}}.faster{{color:#1a7f37}}.slower{{color:#cf222e}}\
+         .low{{color:#cf222e;font-weight:bold}}</style></head><body>"
+    )?;
+    writeln!(out, "<h1>Benchmark report</h1>")?;
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags.iter().map(|(k, v)| format!("{}={}", escape(k), escape(v))).collect();
+        writeln!(out, "<p>Tags: <code>{}</code></p>", tags.join("</code> <code>"))?;
+    }
+    writeln!(out, "<p>Mean time per iteration; shorter bars are faster.</p>")?;
+
+    let height = ROW_HEIGHT * rows.len() as f64 + 10.0;
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-size=\"12\">",
+        LABEL_WIDTH + BAR_WIDTH + 120.0,
+        height
+    )?;
+    for (i, (result, ns)) in rows.iter().enumerate() {
+        let y = ROW_HEIGHT * i as f64 + 5.0;
+        writeln!(
+            out,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+            LABEL_WIDTH - 8.0,
+            y + 14.0,
+            escape(&result.name)
+        )?;
+        let Some(ns) = ns else {
+            writeln!(out, "<text x=\"{}\" y=\"{}\" fill=\"#cf222e\">failed</text>", LABEL_WIDTH, y + 14.0)?;
+            continue;
+        };
+        let width = ns / max_ns * BAR_WIDTH;
+        writeln!(
+            out,
+            "<rect x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"14\" fill=\"#0969da\"/>",
+            LABEL_WIDTH,
+            y + 2.0,
+            width
+        )?;
+        if let Some(base) = baseline.and_then(|b| b.get(&result.name)) {
+            writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"5\" fill=\"#8c959f\"/>",
+                LABEL_WIDTH,
+                y + 17.0,
+                base / max_ns * BAR_WIDTH
+            )?;
+        }
+        writeln!(out, "<text x=\"{:.1}\" y=\"{}\">{}</text>", LABEL_WIDTH + width + 6.0, y + 14.0, human_ns(*ns))?;
+    }
+    writeln!(out, "</svg>")?;
+
+    write!(out, "<table><tr><th>Category</th><th>Benchmark</th><th>Mean</th><th>Ops/sec</th><th>Quality</th>")?;
+    if baseline.is_some() {
+        write!(out, "<th>Baseline</th><th>Change</th>")?;
+    }
+    writeln!(out, "</tr>")?;
+    for (result, ns) in &rows {
+        write!(out, "<tr><td>{}</td><td>{}</td>", escape(&result.category), escape(&result.name))?;
+        match (ns, &result.outcome) {
+            (Some(ns), Ok(m)) => write!(out, "<td>{}</td><td>{:.0}</td>", human_ns(*ns), m.ops_per_sec())?,
+            _ => write!(out, "<td class=\"slower\">failed</td><td></td>")?,
+        }
+        match result.quality {
+            Some(Quality::Low) => write!(out, "<td class=\"low\">low</td>")?,
+            Some(quality) => write!(out, "<td>{}</td>", quality.label())?,
+            None => write!(out, "<td></td>")?,
+        }
+        if let Some(baseline) = baseline {
+            match (ns, baseline.get(&result.name)) {
+                (Some(ns), Some(base)) => {
+                    let change = (ns - base) / base * 100.0;
+                    let class = if change <= 0.0 { "faster" } else { "slower" };
+                    write!(out, "<td>{}</td><td class=\"{}\">{:+.1}%</td>", human_ns(*base), class, change)?;
+                }
+                (_, Some(base)) => write!(out, "<td>{}</td><td></td>", human_ns(*base))?,
+                _ => write!(out, "<td>-</td><td></td>")?,
+            }
+        }
+        writeln!(out, "</tr>")?;
+    }
+    writeln!(out, "</table></body></html>")
+}
+
+fn human_ns(ns: f64) -> String {
+    match ns {
+        ns if ns >= 1e9 => format!("{:.2} s", ns / 1e9),
+        ns if ns >= 1e6 => format!("{:.2} ms", ns / 1e6),
+        ns if ns >= 1e3 => format!("{:.2} µs", ns / 1e3),
+        ns => format!("{:.1} ns", ns),
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,57 @@
+//! `--isolate`: each benchmark runs in a fresh copy of the binary, so
+//! allocator state, CPU frequency and lazy statics left behind by one
+//! benchmark cannot leak into the next.
+//!
+//! The parent re-execs itself with the same arguments plus the hidden
+//! `--isolated-child NAME`. The child runs only that benchmark, wrapping its
+//! output between two marker lines, the second carrying the outcome as JSON.
+//! The parent echoes what was printed between the markers and decodes the
+//! outcome; everything else the child printed (headers) is dropped.
+
+use std::env;
+use std::io::{self, Write};
+use std::process::{self, Command};
+
+use crate::{print_row, Failure, Measurement};
+
+const BEGIN: &str = "@@harness-isolated-begin";
+const RESULT: &str = "@@harness-isolated-result ";
+
+/// This process's own arguments, minus `--isolate`, for `--isolate` children.
+pub(crate) fn parent_args() -> Vec<String> {
+    env::args().skip(1).filter(|arg| arg != "--isolate").collect()
+}
+
+/// Runs benchmark `name` in a child process with `args` and returns its outcome.
+pub(crate) fn run_child(name: &str, args: &[String]) -> Result<Measurement, Failure> {
+    let exe = env::current_exe().map_err(|e| Failure::Error(format!("cannot find own binary: {}", e)))?;
+    let output = Command::new(exe)
+        .args(args)
+        .arg("--isolated-child")
+        .arg(name)
+        .output()
+        .map_err(|e| Failure::Error(format!("cannot start child: {}", e)))?;
+    io::stderr().write_all(&output.stderr).ok();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines().skip_while(|line| *line != BEGIN).skip(1);
+    for line in lines.by_ref() {
+        if let Some(json) = line.strip_prefix(RESULT) {
+            return serde_json::from_str(json)
+                .unwrap_or_else(|e| Err(Failure::Error(format!("bad child result: {}", e))));
+        }
+        print_row(line);
+    }
+    Err(Failure::Error(format!("child exited with {} before reporting", output.status)))
+}
+
+/// Child side: runs `f` between the markers, reports its outcome and exits
+/// without returning to the rest of `main`.
+pub(crate) fn run_as_child(f: impl FnOnce() -> Result<Measurement, Failure>) -> ! {
+    println!("{}", BEGIN);
+    let outcome = f();
+    let json = serde_json::to_string(&outcome).expect("Failed to encode result");
+    println!("{}{}", RESULT, json);
+    io::stdout().flush().ok();
+    process::exit(0);
+}
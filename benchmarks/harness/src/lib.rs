@@ -0,0 +1,49 @@
+//! Shared measurement harness for the Rust comparison benchmarks.
+//!
+//! Binaries flatten [`Options`] into their own clap parser and time their
+//! workload through [`Bencher`], so every benchmark gets the same repeat and
+//! convergence behaviour. [`Suite`] runs a set of named benchmarks with
+//! per-benchmark panic isolation; its binaries flatten [`SuiteOptions`],
+//! which adds the report, history and ordering flags. [`Corpus`] loads input files once per
+//! process so repeats time the workload rather than file I/O.
+
+mod alloc;
+mod bencher;
+mod color;
+mod compare;
+mod corpus;
+mod dump;
+mod format;
+mod history;
+mod html;
+mod isolate;
+mod options;
+mod outdir;
+mod priority;
+mod profile;
+mod progress;
+mod quality;
+mod reference;
+mod report;
+mod runfile;
+pub mod retry;
+pub mod rng;
+pub mod stats;
+mod suite;
+mod validate;
+
+pub use alloc::allocator;
+pub use bencher::{Bencher, Measurement};
+pub use color::{print_row, ColorChoice};
+pub use compare::{print_comparison, Sibling};
+pub use corpus::Corpus;
+pub use dump::SampleDump;
+pub use format::Format;
+pub use options::{Options, SuiteOptions};
+pub use priority::Priority;
+pub use profile::check_build_profile;
+pub use quality::{spread_percent, Quality};
+pub use reference::reference_factor;
+pub use report::{write_prometheus_gauge, Report};
+pub use validate::{check_range, in_range};
+pub use suite::{by_category, print_summary, BenchmarkResult, Failure, Suite};
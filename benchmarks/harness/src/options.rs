@@ -0,0 +1,245 @@
+use std::ops::Deref;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Args;
+
+use crate::{check_range, in_range, ColorChoice, Format, Priority};
+
+/// Harness flags shared by every benchmark binary.
+#[derive(Args, Debug, Clone)]
+pub struct Options {
+    /// Run even when built without optimizations (timings will be misleading)
+    #[arg(long)]
+    pub allow_debug: bool,
+
+    /// Run each workload once with its correctness checks and no timing;
+    /// exits with status 1 if any benchmark fails (debug builds are allowed)
+    #[arg(long, conflicts_with_all = [
+        "duration", "repeat", "repeat_until_stable", "warmup_time", "coordinated_omission_safe",
+    ])]
+    pub check_only: bool,
+
+    /// Re-read the input corpus inside every measured iteration
+    #[arg(long)]
+    pub include_io: bool,
+
+    /// Skip warmup and report the first measured iteration as the cold-start time
+    #[arg(long)]
+    pub no_warmup: bool,
+
+    /// Warm up long enough that the measured loop pays no first-allocation costs
+    #[arg(long, conflicts_with = "no_warmup")]
+    pub steady_state: bool,
+
+    /// Warm up for a fixed time (e.g. 500ms) instead of a fixed iteration count
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, conflicts_with_all = ["no_warmup", "steady_state"])]
+    pub warmup_time: Option<Duration>,
+
+    /// Run each measured loop for a fixed time (e.g. 5s, 500ms) instead of a fixed iteration count
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub duration: Option<Duration>,
+
+    /// Start iterations on a fixed --target-rate schedule and record each one's
+    /// latency from its scheduled start, so stalls are not hidden by the
+    /// iterations they delay (coordinated omission)
+    #[arg(long, requires = "target_rate")]
+    pub coordinated_omission_safe: bool,
+
+    /// Iterations per second to schedule under --coordinated-omission-safe
+    #[arg(long, value_name = "OPS", requires = "coordinated_omission_safe", value_parser = in_range(1.0, 1e9))]
+    pub target_rate: Option<f64>,
+
+    /// Raise the measuring thread's scheduling priority while measuring
+    /// (Linux; needs root or CAP_SYS_NICE, otherwise a no-op)
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub priority: Option<Priority>,
+
+    /// Time an empty workload once and subtract that harness overhead from
+    /// every sample and average (matters for nanosecond-scale workloads)
+    #[arg(long)]
+    pub measure_overhead: bool,
+
+    /// Repeat the full measurement K times and report the mean
+    #[arg(long, default_value_t = 1, value_name = "K", value_parser = in_range(1usize, 10_000))]
+    pub repeat: usize,
+
+    /// Keep repeating until the running mean settles (ignores --repeat)
+    #[arg(long)]
+    pub repeat_until_stable: bool,
+
+    /// Percent change in the running mean below which a run counts as stable
+    #[arg(long, default_value_t = 1.0, value_name = "PERCENT", value_parser = in_range(0.001, 100.0))]
+    pub stable_threshold: f64,
+
+    /// Upper bound on repeats for --repeat-until-stable
+    #[arg(long, default_value_t = 20, value_name = "N", value_parser = in_range(2usize, 10_000))]
+    pub max_repeats: usize,
+
+    /// Decimal places in printed timings and ratios
+    #[arg(long, default_value_t = 2, value_name = "N", value_parser = in_range(0usize, 9))]
+    pub precision: usize,
+
+    /// Internal: run only this benchmark and report it to an --isolate parent
+    #[arg(long, hide = true, value_name = "NAME")]
+    pub isolated_child: Option<String>,
+}
+
+/// Flags for binaries that run their benchmarks through [`Suite`](crate::Suite):
+/// everything in [`Options`] plus the run-level flags (reports, history,
+/// isolation, ordering) that only a suite acts on. Dereferences to the
+/// [`Options`] it contains, so a suite binary can still hand it to [`Bencher`](crate::Bencher).
+#[derive(Args, Debug, Clone)]
+pub struct SuiteOptions {
+    #[command(flatten)]
+    pub options: Options,
+
+    /// Run the whole suite N times and report each benchmark's spread across runs
+    #[arg(long, default_value_t = 1, value_name = "N", value_parser = in_range(1usize, 1000), conflicts_with = "check_only")]
+    pub suite_repeats: usize,
+
+    /// Sleep this long between benchmarks so the CPU can cool (e.g. 3s)
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, conflicts_with = "check_only")]
+    pub cooldown: Option<Duration>,
+
+    /// Spread (coefficient of variation, percent) at or below which a result is graded high quality
+    #[arg(long, default_value_t = 5.0, value_name = "PERCENT", value_parser = in_range(0.0, 1000.0))]
+    pub high_quality_cv: f64,
+
+    /// Spread above which a result is graded low quality
+    #[arg(long, default_value_t = 25.0, value_name = "PERCENT", value_parser = in_range(0.0, 1000.0))]
+    pub low_quality_cv: f64,
+
+    /// Exit with status 3 if any result's spread (the quality-grade CV) is above this
+    #[arg(long, value_name = "PERCENT", value_parser = in_range(0.0, 1000.0), conflicts_with = "check_only")]
+    pub fail_on_variance: Option<f64>,
+
+    /// Results with fewer measured iterations than this are graded low quality
+    #[arg(long, default_value_t = 10, value_name = "N", value_parser = in_range(1usize, 1_000_000))]
+    pub min_samples: usize,
+
+    /// Write every measured iteration's duration to a CSV file
+    #[arg(long, value_name = "PATH", conflicts_with = "check_only")]
+    pub dump_samples: Option<PathBuf>,
+
+    /// Result format: the live table, or a machine-readable report at the end
+    #[arg(long, value_enum, default_value_t = Format::Table)]
+    pub format: Format,
+
+    /// Attach KEY=VALUE to every result in the --format report (repeatable)
+    #[arg(long = "tag", value_name = "KEY=VALUE", value_parser = parse_tag)]
+    pub tags: Vec<(String, String)>,
+
+    /// Multiply times by F to estimate them on the reference machine, and
+    /// list both after the table
+    #[arg(long, value_name = "F", value_parser = in_range(0.001, 1000.0))]
+    pub reference_factor: Option<f64>,
+
+    /// Work out --reference-factor by timing a fixed calibration workload
+    #[arg(long, conflicts_with = "reference_factor")]
+    pub calibrate_reference: bool,
+
+    /// Previous --format csv report to compare against (instead of the
+    /// latest --set-baseline run in the --sqlite database)
+    #[arg(long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+
+    /// After the table, list every result as a multiple of this benchmark's time
+    #[arg(long, value_name = "NAME")]
+    pub relative_to: Option<String>,
+
+    /// Mark the slowest table row and dim the fastest; bare --color means auto
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Never, num_args = 0..=1, default_missing_value = "auto")]
+    pub color: ColorChoice,
+
+    /// Show a progress bar on stderr naming the running benchmark (only on a
+    /// terminal, and not while a --format report goes to stdout)
+    #[arg(long)]
+    pub progress: bool,
+
+    /// Also append this run's results to a SQLite database (created if absent)
+    #[arg(long, value_name = "PATH", conflicts_with = "check_only")]
+    pub sqlite: Option<PathBuf>,
+
+    /// Mark this run as the baseline in the --sqlite database; later runs
+    /// without --baseline are compared against the latest one
+    #[arg(long, requires = "sqlite")]
+    pub set_baseline: bool,
+
+    /// Also write one JSON file per benchmark and a summary.json index here
+    #[arg(long, value_name = "DIR", conflicts_with = "check_only")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Write the --format report to a file instead of stdout
+    #[arg(long, value_name = "PATH", conflicts_with = "check_only")]
+    pub output: Option<PathBuf>,
+
+    /// Run each benchmark in its own child process (slower, but no state is
+    /// shared between benchmarks)
+    #[arg(long, conflicts_with = "isolated_child")]
+    pub isolate: bool,
+
+    /// Run the campaign of benchmarks and parameters listed in a TOML or JSON file
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["isolate", "isolated_child"])]
+    pub run_file: Option<PathBuf>,
+
+    /// Run benchmarks in a random order instead of registration order
+    #[arg(long)]
+    pub shuffle: bool,
+
+    /// Seed for --shuffle (defaults to one derived from the clock, which is reported)
+    #[arg(long, requires = "shuffle")]
+    pub seed: Option<u64>,
+}
+
+impl Deref for SuiteOptions {
+    type Target = Options;
+
+    fn deref(&self) -> &Options {
+        &self.options
+    }
+}
+
+// Report fields a tag would shadow
+const RESERVED_TAGS: &[&str] = &[
+    "category", "name", "benchmark", "status", "iterations", "avg_ns", "ops_per_sec", "spread_percent", "quality",
+    "error", "tags",
+];
+
+/// Parses `KEY=VALUE`. Keys are Prometheus label names (`[A-Za-z_][A-Za-z0-9_]*`)
+/// so a tag can be attached to every metric as-is, and values must fit in an
+/// unquoted CSV field.
+fn parse_tag(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s.split_once('=').ok_or_else(|| format!("expected KEY=VALUE, got `{}`", s))?;
+    let valid = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(format!("tag key `{}` must be letters, digits and _ (not starting with a digit)", key));
+    }
+    if RESERVED_TAGS.contains(&key) {
+        return Err(format!("tag key `{}` is already a report field", key));
+    }
+    // CSV rows are written unquoted
+    if value.contains([',', '"', '\n']) {
+        return Err(format!("tag value `{}` must not contain commas, quotes or newlines", value));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Parses `500ms`, `5s`, `2m`, `250us` or a bare number of seconds, between
+/// 1µs and one hour.
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value.parse().map_err(|_| format!("invalid duration `{}`", s))?;
+    let secs = match unit {
+        "" | "s" => value,
+        "ms" => value / 1_000.0,
+        "us" | "µs" => value / 1_000_000.0,
+        "m" => value * 60.0,
+        _ => return Err(format!("unknown duration unit `{}` (use us, ms, s or m)", unit)),
+    };
+    check_range(secs, 0.000_001, 3600.0).map_err(|_| format!("must be between 1us and 1h, got {}", s))?;
+    Ok(Duration::from_secs_f64(secs))
+}
@@ -0,0 +1,98 @@
+//! `--output-dir DIR`: one JSON file per benchmark plus a `summary.json`
+//! index, so a single benchmark's history can be diffed on its own.
+//!
+//! Each `DIR/<name>.json` holds the same object as that benchmark's
+//! `--format jsonl` line and is written as the benchmark finishes. Names are
+//! made filesystem-safe (anything outside `A-Z a-z 0-9 . _ -` becomes `_`)
+//! and de-duplicated with a numeric suffix. `summary.json` lists every
+//! benchmark with its status, mean time and file, and is written last.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::report::{unique_tags, JsonLine};
+use crate::{BenchmarkResult, Failure, SuiteOptions};
+
+pub(crate) struct OutputDir {
+    dir: PathBuf,
+    tags: Vec<(String, String)>,
+    /// File name written for each recorded benchmark, in run order.
+    files: Vec<String>,
+    taken: HashSet<String>,
+}
+
+#[derive(Serialize)]
+struct SummaryEntry<'a> {
+    benchmark: &'a str,
+    category: &'a str,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avg_ns: Option<f64>,
+    file: &'a str,
+}
+
+impl OutputDir {
+    /// Creates the directory, or returns `None` without `--output-dir`.
+    pub(crate) fn create(opts: &SuiteOptions) -> io::Result<Option<Self>> {
+        let Some(dir) = &opts.output_dir else {
+            return Ok(None);
+        };
+        fs::create_dir_all(dir)?;
+        Ok(Some(Self {
+            dir: dir.clone(),
+            tags: unique_tags(&opts.tags),
+            files: Vec::new(),
+            taken: HashSet::from(["summary.json".to_string()]),
+        }))
+    }
+
+    /// Writes one finished benchmark's file.
+    pub(crate) fn record(&mut self, result: &BenchmarkResult) -> io::Result<()> {
+        let file = self.file_name(&result.name);
+        let json = serde_json::to_string_pretty(&JsonLine::new(result, &self.tags))?;
+        fs::write(self.dir.join(&file), json + "\n")?;
+        self.files.push(file);
+        Ok(())
+    }
+
+    /// Writes `summary.json` for the recorded benchmarks.
+    pub(crate) fn finish(self, results: &[BenchmarkResult]) -> io::Result<()> {
+        let entries: Vec<SummaryEntry> = results
+            .iter()
+            .zip(&self.files)
+            .map(|(r, file)| SummaryEntry {
+                benchmark: &r.name,
+                category: &r.category,
+                status: match &r.outcome {
+                    Ok(_) => "ok",
+                    Err(Failure::Error(_)) => "error",
+                    Err(Failure::Panic(_)) => "panic",
+                },
+                avg_ns: r.outcome.as_ref().ok().map(|m| m.per_iter().as_secs_f64() * 1e9),
+                file,
+            })
+            .collect();
+        let json = serde_json::to_string_pretty(&entries)?;
+        fs::write(self.dir.join("summary.json"), json + "\n")
+    }
+
+    // `Word Boundary` -> `Word_Boundary.json`; a second `Word Boundary` (or a
+    // benchmark named `summary`) gets `-2`
+    fn file_name(&mut self, name: &str) -> String {
+        let stem: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '_' })
+            .collect();
+        let mut file = format!("{}.json", stem);
+        let mut n = 2;
+        while !self.taken.insert(file.clone()) {
+            file = format!("{}-{}.json", stem, n);
+            n += 1;
+        }
+        file
+    }
+}
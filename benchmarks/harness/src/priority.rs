@@ -0,0 +1,137 @@
+//! `--priority`: raises the measuring thread's scheduling priority while a
+//! [`Bencher`](crate::Bencher) warms up and measures, then puts it back, so
+//! background tasks preempt the workload less often.
+//!
+//! Linux only, and both modes need privileges (root or `CAP_SYS_NICE`;
+//! `nice` also works within `RLIMIT_NICE`). Without them, or on other
+//! platforms, the flag is a no-op apart from a note on stderr. Linux
+//! priorities are per thread: threads the workload starts while raised
+//! inherit the priority, threads started earlier (e.g. an existing rayon
+//! pool) do not.
+
+use std::sync::Once;
+
+use clap::ValueEnum;
+
+/// Scheduling priority to run measurements at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Priority {
+    /// The highest nice value (-20) under the normal scheduler
+    Nice,
+    /// The lowest real-time priority under SCHED_FIFO, which ordinary tasks
+    /// cannot preempt (the kernel's RT throttling still leaves them ~5%)
+    Fifo,
+}
+
+impl Priority {
+    fn describe(self) -> &'static str {
+        match self {
+            Priority::Nice => "nice -20",
+            Priority::Fifo => "SCHED_FIFO",
+        }
+    }
+}
+
+/// Holds a raised priority; dropping it restores the previous one.
+pub(crate) struct Raised(Option<sys::Previous>);
+
+impl Drop for Raised {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            sys::restore(previous);
+        }
+    }
+}
+
+/// Raises the calling thread to `priority` until the guard is dropped.
+/// `None` leaves it alone. The outcome is printed to stderr once per process.
+pub(crate) fn raise(priority: Option<Priority>) -> Raised {
+    let Some(priority) = priority else {
+        return Raised(None);
+    };
+    let outcome = sys::raise(priority);
+    static REPORTED: Once = Once::new();
+    REPORTED.call_once(|| match &outcome {
+        Ok(_) => eprintln!("Priority: {} while measuring", priority.describe()),
+        Err(e) => eprintln!("Priority: {} not applied ({}), running at the default priority", priority.describe(), e),
+    });
+    Raised(outcome.ok())
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::io;
+    use std::mem;
+
+    use super::Priority;
+
+    pub(crate) enum Previous {
+        Nice(libc::c_int),
+        Policy(libc::c_int, libc::sched_param),
+    }
+
+    // pid/tid 0 is the calling thread for all of these on Linux
+    pub(crate) fn raise(priority: Priority) -> io::Result<Previous> {
+        unsafe {
+            match priority {
+                Priority::Nice => {
+                    // -1 is a valid nice value, so errors show up only in errno
+                    *libc::__errno_location() = 0;
+                    let old = libc::getpriority(libc::PRIO_PROCESS, 0);
+                    if old == -1 && *libc::__errno_location() != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                    if libc::setpriority(libc::PRIO_PROCESS, 0, -20) != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                    Ok(Previous::Nice(old))
+                }
+                Priority::Fifo => {
+                    let policy = libc::sched_getscheduler(0);
+                    let mut old: libc::sched_param = mem::zeroed();
+                    if policy < 0 || libc::sched_getparam(0, &mut old) != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                    let param = libc::sched_param {
+                        sched_priority: libc::sched_get_priority_min(libc::SCHED_FIFO),
+                    };
+                    if libc::sched_setscheduler(0, libc::SCHED_FIFO, &param) != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                    Ok(Previous::Policy(policy, old))
+                }
+            }
+        }
+    }
+
+    // Lowering a priority is always permitted, so this cannot fail in practice
+    pub(crate) fn restore(previous: Previous) {
+        unsafe {
+            match previous {
+                Previous::Nice(old) => {
+                    libc::setpriority(libc::PRIO_PROCESS, 0, old);
+                }
+                Previous::Policy(policy, old) => {
+                    libc::sched_setscheduler(0, policy, &old);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use std::io;
+
+    use super::Priority;
+
+    pub(crate) enum Previous {}
+
+    pub(crate) fn raise(_: Priority) -> io::Result<Previous> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "only supported on Linux"))
+    }
+
+    pub(crate) fn restore(previous: Previous) {
+        match previous {}
+    }
+}
@@ -0,0 +1,30 @@
+use std::sync::Once;
+
+/// Rough release-over-debug speedup seen across the regex and json benchmarks.
+const DEBUG_SLOWDOWN_ESTIMATE: u32 = 20;
+
+/// Refuses to benchmark a debug build unless `allow_debug` is set, in which
+/// case it prints a warning banner. Runs at most once per process.
+///
+/// Harness-driven binaries get this automatically from [`crate::Bencher::new`];
+/// binaries that time themselves call it directly.
+pub fn check_build_profile(allow_debug: bool) {
+    static CHECKED: Once = Once::new();
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    CHECKED.call_once(|| {
+        let rule = "!".repeat(70);
+        eprintln!("{}", rule);
+        eprintln!("!! DEBUG BUILD: timings are not representative.");
+        eprintln!(
+            "!! A release build would be roughly {}x faster; rebuild with `cargo build --release`.",
+            DEBUG_SLOWDOWN_ESTIMATE
+        );
+        eprintln!("{}", rule);
+        if !allow_debug {
+            eprintln!("Refusing to benchmark a debug build (pass --allow-debug to run anyway).");
+            std::process::exit(2);
+        }
+    });
+}
@@ -0,0 +1,57 @@
+//! `--progress`: a bar on stderr showing which benchmark is running and how
+//! many of the suite's runs have finished.
+//!
+//! The bar is only drawn when stderr is a terminal and stdout is not carrying
+//! a machine-readable report, so redirected and piped runs are unchanged.
+//! Table rows printed through [`crate::print_row`] are drawn above the bar.
+
+use std::io::{self, IsTerminal};
+use std::sync::{Mutex, PoisonError};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::{Format, SuiteOptions};
+
+static BAR: Mutex<Option<ProgressBar>> = Mutex::new(None);
+
+/// Shows the bar for `total` benchmark runs, if this run wants one.
+pub(crate) fn start(opts: &SuiteOptions, total: usize) {
+    let report_on_stdout = opts.format != Format::Table && opts.output.is_none();
+    if !opts.progress || opts.isolated_child.is_some() || report_on_stdout || !io::stderr().is_terminal() {
+        return;
+    }
+    let bar = ProgressBar::new(total as u64).with_style(
+        ProgressStyle::with_template("{bar:30} {pos}/{len} [{elapsed}] {msg}").expect("valid progress template"),
+    );
+    *BAR.lock().unwrap_or_else(PoisonError::into_inner) = Some(bar);
+}
+
+/// Names the benchmark that is about to run.
+pub(crate) fn running(name: &str) {
+    if let Some(bar) = BAR.lock().unwrap_or_else(PoisonError::into_inner).as_ref() {
+        bar.set_message(name.to_string());
+    }
+}
+
+/// Counts one finished benchmark run.
+pub(crate) fn advance() {
+    if let Some(bar) = BAR.lock().unwrap_or_else(PoisonError::into_inner).as_ref() {
+        bar.inc(1);
+    }
+}
+
+/// Removes the bar once the suite is done.
+pub(crate) fn finish() {
+    if let Some(bar) = BAR.lock().unwrap_or_else(PoisonError::into_inner).take() {
+        bar.finish_and_clear();
+    }
+}
+
+/// Runs `f` with the bar hidden, so whatever it prints is not drawn over.
+pub(crate) fn suspend<R>(f: impl FnOnce() -> R) -> R {
+    let bar = BAR.lock().unwrap_or_else(PoisonError::into_inner).clone();
+    match bar {
+        Some(bar) => bar.suspend(f),
+        None => f(),
+    }
+}
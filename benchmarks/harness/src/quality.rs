@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::stats::cv_percent;
+use crate::{Measurement, SuiteOptions};
+
+/// How far a result can be trusted, from its spread and sample count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Quality {
+    High,
+    Medium,
+    Low,
+}
+
+impl Quality {
+    /// Grades a measurement against `--high-quality-cv`, `--low-quality-cv`
+    /// and `--min-samples`.
+    ///
+    /// With three or more repeats the spread is taken across repeat means,
+    /// which is what a cited number averages over; otherwise across the means
+    /// of consecutive batches of iterations. Too few samples is always `Low`.
+    pub fn assess(m: &Measurement, opts: &SuiteOptions) -> Self {
+        let cv = spread_percent(m);
+        let samples: usize = m.samples.iter().map(Vec::len).sum();
+        if samples < opts.min_samples || cv > opts.low_quality_cv {
+            Quality::Low
+        } else if cv > opts.high_quality_cv {
+            Quality::Medium
+        } else {
+            Quality::High
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Quality::High => "high",
+            Quality::Medium => "medium",
+            Quality::Low => "low",
+        }
+    }
+}
+
+/// Consecutive batches a single measured loop is split into when there are
+/// too few repeats to compare.
+const BATCHES: usize = 10;
+
+/// The coefficient of variation [`Quality::assess`] grades, in percent.
+pub fn spread_percent(m: &Measurement) -> f64 {
+    let mean = |s: &[Duration]| s.iter().map(Duration::as_secs_f64).sum::<f64>() / s.len().max(1) as f64;
+    let means: Vec<f64> = if m.samples.len() >= 3 {
+        m.samples.iter().map(|s| mean(s)).collect()
+    } else {
+        // Single iterations are dominated by timer resolution and interrupts,
+        // so compare batch means instead, the way repeats would be compared
+        m.samples
+            .iter()
+            .flat_map(|s| s.chunks(s.len().div_ceil(BATCHES).max(1)))
+            .map(mean)
+            .collect()
+    };
+    cv_percent(&means)
+}
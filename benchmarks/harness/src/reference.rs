@@ -0,0 +1,65 @@
+//! Normalizing times to a reference machine, so numbers taken on a laptop
+//! and on a CI box can be compared roughly.
+//!
+//! The factor multiplies this machine's times into the reference machine's:
+//! 0.5 means the reference runs everything in half the time. Pass it as
+//! `--reference-factor F`, or let `--calibrate-reference` work it out by
+//! timing a fixed single-threaded integer workload and dividing the stored
+//! reference time by this machine's. One scalar cannot capture memory or
+//! SIMD differences, so normalized numbers are estimates; raw numbers stay
+//! the ones to cite.
+
+use std::hint::black_box;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::SuiteOptions;
+
+/// The calibration workload's best time on the reference machine. When the
+/// reference machine changes, run a suite binary (`bench_rust`) there with
+/// `--calibrate-reference` and copy the "here" time from its stderr line.
+const REFERENCE_TIME: Duration = Duration::from_millis(90);
+
+const ROUNDS: usize = 5;
+const STEPS: u64 = 40_000_000;
+
+/// The `--reference-factor`, or the `--calibrate-reference` result (worked
+/// out once per process), or `None` when neither was given.
+pub fn reference_factor(opts: &SuiteOptions) -> Option<f64> {
+    if let Some(factor) = opts.reference_factor {
+        return Some(factor);
+    }
+    if !opts.calibrate_reference {
+        return None;
+    }
+    static CALIBRATED: OnceLock<f64> = OnceLock::new();
+    Some(*CALIBRATED.get_or_init(|| {
+        let here = calibrate();
+        let factor = REFERENCE_TIME.as_secs_f64() / here.as_secs_f64();
+        eprintln!(
+            "Reference calibration: {:.1} ms here, {:.1} ms on the reference machine, factor {:.3}",
+            here.as_secs_f64() * 1000.0,
+            REFERENCE_TIME.as_secs_f64() * 1000.0,
+            factor
+        );
+        factor
+    }))
+}
+
+// Best of a few rounds of a dependent multiply-xorshift chain: one core, no
+// memory traffic, nothing the optimizer can skip
+fn calibrate() -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            let mut x = black_box(0x9e37_79b9_7f4a_7c15u64);
+            for _ in 0..STEPS {
+                x = x.wrapping_mul(0x2545_f491_4f6c_dd1d);
+                x ^= x >> 29;
+            }
+            black_box(x);
+            start.elapsed()
+        })
+        .min()
+        .expect("at least one round")
+}
@@ -0,0 +1,368 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::html::write_html;
+use crate::suite::by_category;
+use crate::{spread_percent, BenchmarkResult, Failure, Format, Measurement, Quality, SuiteOptions};
+
+/// Sink for the machine-readable `--format`s, fed by [`crate::Suite::run`].
+/// Table output is left to each binary, which prints its rows as benchmarks
+/// finish.
+///
+/// Output is kept parseable if the run dies partway: CSV rows and JSON lines
+/// are written and flushed as each benchmark completes, so a file cut short still holds
+/// every finished result. Prometheus families can only be written once all
+/// results are in, so under `--output` they go to a `.partial` file that is
+/// renamed into place at the end; an interrupted run leaves no half-written
+/// metrics file behind.
+///
+/// HTML and Bencher Metric Format reports are handled the same way as
+/// Prometheus ones.
+///
+/// Prometheus metric names are part of the CI interface and are listed in
+/// the harness README; add new metrics rather than renaming existing ones.
+pub struct Report {
+    format: Format,
+    out: Box<dyn Write>,
+    /// `(partial, final)` paths when Prometheus or HTML output goes to a file.
+    rename: Option<(PathBuf, PathBuf)>,
+    /// `--baseline` mean ns per iteration, by benchmark name.
+    baseline: Option<HashMap<String, f64>>,
+    /// `--tag` pairs, in command-line order, one per key.
+    tags: Vec<(String, String)>,
+}
+
+impl Report {
+    /// Opens `--output` (or stdout) and writes any header, or returns `None`
+    /// for table output.
+    pub fn create(opts: &SuiteOptions) -> io::Result<Option<Self>> {
+        if opts.format == Format::Table {
+            return Ok(None);
+        }
+        let mut rename = None;
+        let out: Box<dyn Write> = match &opts.output {
+            None => Box::new(io::stdout()),
+            Some(path) if matches!(opts.format, Format::Prometheus | Format::Html | Format::Bencher) => {
+                let mut partial = path.clone().into_os_string();
+                partial.push(".partial");
+                let partial = PathBuf::from(partial);
+                let file = File::create(&partial)?;
+                rename = Some((partial, path.clone()));
+                Box::new(BufWriter::new(file))
+            }
+            Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        };
+        let baseline = opts.baseline.as_deref().map(load_baseline).transpose()?;
+        let mut report = Self {
+            format: opts.format,
+            out,
+            rename,
+            baseline,
+            tags: unique_tags(&opts.tags),
+        };
+        if report.format == Format::Csv {
+            // Tags become trailing columns, repeated on every row
+            write!(report.out, "category,benchmark,status,iterations,avg_ns,ops_per_sec,spread_percent,quality")?;
+            for (key, _) in &report.tags {
+                write!(report.out, ",{}", key)?;
+            }
+            writeln!(report.out)?;
+            report.out.flush()?;
+        }
+        Ok(Some(report))
+    }
+
+    /// Compares against `baseline` when no `--baseline` file was given.
+    pub(crate) fn default_baseline(&mut self, baseline: &HashMap<String, f64>) {
+        self.baseline.get_or_insert_with(|| baseline.clone());
+    }
+
+    /// Records one finished benchmark.
+    pub fn record(&mut self, result: &BenchmarkResult) -> io::Result<()> {
+        match self.format {
+            Format::Csv => self.record_csv(result),
+            Format::Jsonl => {
+                serde_json::to_writer(&mut self.out, &JsonLine::new(result, &self.tags))?;
+                writeln!(self.out)?;
+                self.out.flush()
+            }
+            Format::Table | Format::Prometheus | Format::Html | Format::Bencher => Ok(()),
+        }
+    }
+
+    fn record_csv(&mut self, result: &BenchmarkResult) -> io::Result<()> {
+        write!(self.out, "{},", result.category)?;
+        match &result.outcome {
+            Ok(m) => write!(
+                self.out,
+                "{},ok,{},{:.1},{:.0},{:.2},{}",
+                result.name,
+                m.iterations(),
+                m.per_iter().as_secs_f64() * 1e9,
+                m.ops_per_sec(),
+                spread_percent(m),
+                result.quality.map_or("", Quality::label)
+            )?,
+            Err(Failure::Error(_)) => write!(self.out, "{},error,,,,,", result.name)?,
+            Err(Failure::Panic(_)) => write!(self.out, "{},panic,,,,,", result.name)?,
+        }
+        for (_, value) in &self.tags {
+            write!(self.out, ",{}", value)?;
+        }
+        writeln!(self.out)?;
+        self.out.flush()
+    }
+
+    /// Writes anything that needs the full result set, flushes and closes.
+    pub fn finish(mut self, results: &[BenchmarkResult]) -> io::Result<()> {
+        match self.format {
+            Format::Prometheus => write_prometheus(results, &self.tags, &mut self.out)?,
+            Format::Html => write_html(&mut self.out, results, &self.tags, self.baseline.as_ref())?,
+            Format::Bencher => write_bmf(results, &mut self.out)?,
+            Format::Table | Format::Csv | Format::Jsonl => {}
+        }
+        self.out.flush()?;
+        drop(self.out);
+        if let Some((partial, path)) = self.rename {
+            fs::rename(partial, path)?;
+        }
+        Ok(())
+    }
+}
+
+// A repeated --tag key keeps its last value, at its first position
+pub(crate) fn unique_tags(tags: &[(String, String)]) -> Vec<(String, String)> {
+    let mut unique: Vec<(String, String)> = Vec::new();
+    for (key, value) in tags {
+        match unique.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => v.clone_from(value),
+            None => unique.push((key.clone(), value.clone())),
+        }
+    }
+    unique
+}
+
+/// One `--format jsonl` line: the CSV columns, the failure message and any
+/// `--tag`s.
+#[derive(Serialize)]
+pub(crate) struct JsonLine<'a> {
+    category: &'a str,
+    benchmark: &'a str,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iterations: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avg_ns: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ops_per_sec: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spread_percent: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quality: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    tags: BTreeMap<&'a str, &'a str>,
+}
+
+impl<'a> JsonLine<'a> {
+    pub(crate) fn new(result: &'a BenchmarkResult, tags: &'a [(String, String)]) -> Self {
+        let ok = result.outcome.as_ref().ok();
+        let (status, error) = match &result.outcome {
+            Ok(_) => ("ok", None),
+            Err(Failure::Error(message)) => ("error", Some(message.as_str())),
+            Err(Failure::Panic(message)) => ("panic", Some(message.as_str())),
+        };
+        Self {
+            category: &result.category,
+            benchmark: &result.name,
+            status,
+            iterations: ok.map(Measurement::iterations),
+            avg_ns: ok.map(|m| m.per_iter().as_secs_f64() * 1e9),
+            ops_per_sec: ok.map(Measurement::ops_per_sec),
+            spread_percent: ok.map(spread_percent),
+            quality: result.quality.map(Quality::label),
+            error,
+            tags: tags.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect(),
+        }
+    }
+}
+
+/// Reads mean ns per iteration by benchmark name from a `--format csv` report.
+pub(crate) fn load_baseline(path: &Path) -> io::Result<HashMap<String, f64>> {
+    let text = fs::read_to_string(path)?;
+    let mut lines = text.lines();
+    let header: Vec<&str> = lines.next().unwrap_or_default().split(',').collect();
+    let column = |name: &str| {
+        header.iter().position(|h| *h == name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: baseline CSV has no `{}` column", path.display(), name),
+            )
+        })
+    };
+    let (name, avg_ns) = (column("benchmark")?, column("avg_ns")?);
+    Ok(lines
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            let ns = fields.get(avg_ns)?.parse().ok()?;
+            Some((fields.get(name)?.to_string(), ns))
+        })
+        .collect())
+}
+
+fn write_prometheus(results: &[BenchmarkResult], tags: &[(String, String)], out: &mut dyn Write) -> io::Result<()> {
+    write_prometheus_gauge(
+        out,
+        "metal0_benchmark_avg_ns",
+        "Mean time per iteration in nanoseconds",
+        &per_benchmark(results, tags, |m| m.per_iter().as_secs_f64() * 1e9),
+    )?;
+    write_prometheus_gauge(
+        out,
+        "metal0_benchmark_ops_per_second",
+        "Iterations per second of measured time",
+        &per_benchmark(results, tags, |m| m.ops_per_sec()),
+    )?;
+    write_prometheus_gauge(
+        out,
+        "metal0_benchmark_iterations",
+        "Mean iterations per measured loop",
+        &per_benchmark(results, tags, |m| m.iterations() as f64),
+    )?;
+    write_prometheus_gauge(
+        out,
+        "metal0_benchmark_spread_percent",
+        "Coefficient of variation the quality grade is based on",
+        &per_benchmark(results, tags, spread_percent),
+    )?;
+    let quality: Vec<_> = by_category(results)
+        .into_iter()
+        .flat_map(|(_, group)| group)
+        .filter_map(|r| {
+            let grade = match r.quality? {
+                Quality::High => 2.0,
+                Quality::Medium => 1.0,
+                Quality::Low => 0.0,
+            };
+            Some((labels(r, tags), grade))
+        })
+        .collect();
+    write_prometheus_gauge(
+        out,
+        "metal0_benchmark_quality",
+        "Trust grade: 2 high, 1 medium, 0 low",
+        &quality,
+    )?;
+    let failed: Vec<_> = by_category(results)
+        .into_iter()
+        .flat_map(|(_, group)| group)
+        .map(|r| (labels(r, tags), if r.outcome.is_err() { 1.0 } else { 0.0 }))
+        .collect();
+    write_prometheus_gauge(
+        out,
+        "metal0_benchmark_failed",
+        "1 if the benchmark failed or panicked",
+        &failed,
+    )
+}
+
+/// One Bencher Metric Format measure: a value with optional bounds.
+#[derive(Serialize)]
+struct BmfMetric {
+    value: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lower_value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upper_value: Option<f64>,
+}
+
+// Successful benchmarks by name, each with `avg_ns` (bounded by the fastest
+// and slowest repeat when there are several) and `ops_per_sec`, named as in
+// the CSV and JSON-lines reports. BMF has no place for failures or tags.
+fn write_bmf(results: &[BenchmarkResult], out: &mut dyn Write) -> io::Result<()> {
+    let mut report = BTreeMap::new();
+    for r in results {
+        let Ok(m) = &r.outcome else {
+            continue;
+        };
+        let repeat_ns: Vec<f64> = m
+            .repeats
+            .iter()
+            .zip(&m.samples)
+            .map(|(total, samples)| total.as_secs_f64() * 1e9 / samples.len().max(1) as f64)
+            .collect();
+        let bound = |pick: fn(f64, f64) -> f64| {
+            (repeat_ns.len() > 1).then(|| repeat_ns.iter().copied().reduce(pick).unwrap_or_default())
+        };
+        let measures = BTreeMap::from([
+            (
+                "avg_ns",
+                BmfMetric {
+                    value: m.per_iter().as_secs_f64() * 1e9,
+                    lower_value: bound(f64::min),
+                    upper_value: bound(f64::max),
+                },
+            ),
+            (
+                "ops_per_sec",
+                BmfMetric {
+                    value: m.ops_per_sec(),
+                    lower_value: None,
+                    upper_value: None,
+                },
+            ),
+        ]);
+        report.insert(r.name.as_str(), measures);
+    }
+    serde_json::to_writer_pretty(&mut *out, &report)?;
+    writeln!(out)
+}
+
+// Category and name, then the --tag pairs
+fn labels<'a>(result: &BenchmarkResult, tags: &'a [(String, String)]) -> Vec<(&'a str, String)> {
+    let mut labels = vec![("category", result.category.clone()), ("name", result.name.clone())];
+    labels.extend(tags.iter().map(|(key, value)| (key.as_str(), value.clone())));
+    labels
+}
+
+// One sample per successful benchmark, labelled with its category and name
+fn per_benchmark<'a>(
+    results: &[BenchmarkResult],
+    tags: &'a [(String, String)],
+    value: impl Fn(&Measurement) -> f64,
+) -> Vec<(Vec<(&'a str, String)>, f64)> {
+    by_category(results)
+        .into_iter()
+        .flat_map(|(_, group)| group)
+        .filter_map(|r| r.outcome.as_ref().ok().map(|m| (labels(r, tags), value(m))))
+        .collect()
+}
+
+/// Writes one gauge family in the Prometheus text exposition format: the
+/// `# HELP` and `# TYPE` lines, then one sample per `(labels, value)`.
+pub fn write_prometheus_gauge(
+    out: &mut dyn Write,
+    name: &str,
+    help: &str,
+    samples: &[(Vec<(&str, String)>, f64)],
+) -> io::Result<()> {
+    writeln!(out, "# HELP {} {}", name, help)?;
+    writeln!(out, "# TYPE {} gauge", name)?;
+    for (labels, value) in samples {
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+            .collect();
+        writeln!(out, "{}{{{}}} {}", name, labels.join(","), value)?;
+    }
+    Ok(())
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
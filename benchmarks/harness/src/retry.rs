@@ -0,0 +1,85 @@
+use std::future::Future;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Bounded retry for single I/O operations in the I/O benchmarks.
+///
+/// A transient error (see [`is_transient`]) is retried immediately, up to
+/// `max` more attempts; any other error, or a transient one that outlasts
+/// the retries, is returned as a hard failure. Retries are counted so the
+/// benchmark can report them: a run that needed many is a loaded machine,
+/// not a fast one. Shared across tasks behind an `Arc`.
+#[derive(Debug)]
+pub struct Retries {
+    max: usize,
+    count: AtomicUsize,
+}
+
+/// Errors worth retrying: would-block, interrupted, timed out, and a
+/// connection reset or aborted by a peer under load.
+pub fn is_transient(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::WouldBlock
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+    )
+}
+
+impl Retries {
+    pub fn new(max: usize) -> Self {
+        Self {
+            max,
+            count: AtomicUsize::new(0),
+        }
+    }
+
+    /// Runs `op`, retrying transient errors.
+    pub fn run<T>(&self, mut op: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+        let mut attempt = 0;
+        loop {
+            match op() {
+                Err(e) if self.should_retry(&e, &mut attempt) => {}
+                result => return result,
+            }
+        }
+    }
+
+    /// Async form of [`Retries::run`]: `op` makes a fresh future per attempt.
+    pub async fn run_async<T, F>(&self, mut op: impl FnMut() -> F) -> io::Result<T>
+    where
+        F: Future<Output = io::Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match op().await {
+                Err(e) if self.should_retry(&e, &mut attempt) => {}
+                result => return result,
+            }
+        }
+    }
+
+    /// For loops that cannot hand the operation over as a closure: whether
+    /// `error` should be retried, given the `attempt` retries already made
+    /// for this operation. A retry is counted and bumps `attempt`.
+    pub fn should_retry(&self, error: &io::Error, attempt: &mut usize) -> bool {
+        if *attempt < self.max && is_transient(error) {
+            *attempt += 1;
+            self.count.fetch_add(1, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+
+    /// Retries made so far, across every operation.
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Retries allowed per operation.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+}
@@ -0,0 +1,199 @@
+//! `--run-file`: a campaign of benchmark runs, each with its own harness
+//! parameters, read from TOML or JSON (by extension) and executed as one
+//! suite with a single combined report.
+//!
+//! ```toml
+//! [[run]]
+//! benchmark = "Email"
+//! params = { repeat = 5, steady-state = true }
+//!
+//! [[run]]
+//! benchmark = "Email"
+//! label = "Email (cold)"
+//! params = { no-warmup = true }
+//! ```
+//!
+//! Params may be any flag of the binary, harness or its own. Each run also
+//! inherits the parent's command line (minus `--run-file`), with its params
+//! replacing any flag the command line already set.
+//!
+//! Every run is checked before the first one starts: the benchmark must
+//! exist and its parameters must parse and pass the same range checks as
+//! the command line.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use clap::{Command, FromArgMatches};
+use serde::Deserialize;
+
+use crate::{ColorChoice, Format, SuiteOptions};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RunFile {
+    run: Vec<RunSpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RunSpec {
+    benchmark: String,
+    label: Option<String>,
+    #[serde(default)]
+    params: BTreeMap<String, Param>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Param {
+    Flag(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+/// One validated run: the benchmark to execute and the arguments its child
+/// process gets.
+pub(crate) struct PlannedRun {
+    pub benchmark: String,
+    pub label: String,
+    pub args: Vec<String>,
+}
+
+/// Reads and validates a run file against the registered benchmark names and
+/// the binary's `command`, returning every problem found rather than just the
+/// first.
+pub(crate) fn load(path: &Path, known: &[&str], command: Command) -> Result<Vec<PlannedRun>, Vec<String>> {
+    let text = fs::read_to_string(path).map_err(|e| vec![format!("{}: {}", path.display(), e)])?;
+    let file: RunFile = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => serde_json::from_str(&text).map_err(|e| e.to_string()),
+        _ => toml::from_str(&text).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| vec![format!("{}: {}", path.display(), e)])?;
+
+    let mut command = command.no_binary_name(true);
+    command.build();
+    let parent: Vec<String> = env::args().skip(1).collect();
+    let mut errors = Vec::new();
+    let mut runs = Vec::new();
+    for (i, spec) in file.run.into_iter().enumerate() {
+        let label = spec.label.unwrap_or_else(|| spec.benchmark.clone());
+        if !known.contains(&spec.benchmark.as_str()) {
+            errors.push(format!("run {} ({}): no benchmark named `{}`", i + 1, label, spec.benchmark));
+        }
+        let own = to_args(&spec.params);
+        let mut args = inherited_args(&command, &parent, &own);
+        args.extend(own.iter().cloned());
+        // The run's own params must leave the campaign flags alone, and
+        // together with the inherited ones must still parse
+        if let Err(e) = check_args(&command, &own).and_then(|_| parse(&command, &args).map(drop)) {
+            errors.push(format!("run {} ({}): {}", i + 1, label, e));
+        }
+        runs.push(PlannedRun {
+            benchmark: spec.benchmark,
+            label,
+            args,
+        });
+    }
+    if errors.is_empty() {
+        Ok(runs)
+    } else {
+        Err(errors)
+    }
+}
+
+fn to_args(params: &BTreeMap<String, Param>) -> Vec<String> {
+    let mut args = Vec::new();
+    for (key, value) in params {
+        let flag = format!("--{}", key.replace('_', "-"));
+        match value {
+            Param::Flag(true) => args.push(flag),
+            Param::Flag(false) => {}
+            Param::Int(n) => args.extend([flag, n.to_string()]),
+            Param::Float(x) => args.extend([flag, x.to_string()]),
+            Param::Text(s) => args.extend([flag, s.clone()]),
+        }
+    }
+    args
+}
+
+// The parent's arguments minus --run-file and minus every flag the run sets
+// in `own`, so a run's params replace the command line's instead of clashing
+// with them
+fn inherited_args(command: &Command, parent: &[String], own: &[String]) -> Vec<String> {
+    let overridden = |name: &str| name == "run-file" || own.iter().any(|arg| arg.strip_prefix("--") == Some(name));
+    let mut args = Vec::new();
+    let mut parent = parent.iter().peekable();
+    while let Some(arg) = parent.next() {
+        let Some(flag) = arg.strip_prefix("--") else {
+            args.push(arg.clone());
+            continue;
+        };
+        let (name, inline_value) = match flag.split_once('=') {
+            Some((name, _)) => (name, true),
+            None => (flag, false),
+        };
+        // A flag whose value is optional (--color) only takes the next
+        // argument when that is not another flag
+        let value = command.get_arguments().find(|a| a.get_long() == Some(name)).and_then(|a| {
+            let range = a.get_num_args().filter(|_| a.get_action().takes_values() && !inline_value)?;
+            match parent.peek() {
+                Some(next) if range.min_values() > 0 || !next.starts_with('-') => parent.next(),
+                _ => None,
+            }
+        });
+        if !overridden(name) {
+            args.push(arg.clone());
+            args.extend(value.cloned());
+        }
+    }
+    args
+}
+
+// Parses arguments exactly as the binary's command line would be parsed
+fn parse(command: &Command, args: &[String]) -> Result<SuiteOptions, String> {
+    let matches = command
+        .clone()
+        .try_get_matches_from(args)
+        .map_err(|e| e.render().to_string().lines().next().unwrap_or_default().to_string())?;
+    SuiteOptions::from_arg_matches(&matches).map_err(|e| e.to_string())
+}
+
+fn check_args(command: &Command, args: &[String]) -> Result<(), String> {
+    let opts = parse(command, args)?;
+    // These shape the whole campaign, so they belong on the command line
+    if opts.run_file.is_some()
+        || opts.isolate
+        || opts.shuffle
+        || opts.output.is_some()
+        || opts.output_dir.is_some()
+        || opts.dump_samples.is_some()
+        || opts.baseline.is_some()
+        || opts.sqlite.is_some()
+        || opts.set_baseline
+        || opts.reference_factor.is_some()
+        || opts.calibrate_reference
+        || opts.color != ColorChoice::Never
+        || opts.progress
+        || opts.relative_to.is_some()
+        || opts.suite_repeats != 1
+        || opts.cooldown.is_some()
+        || !opts.tags.is_empty()
+        || opts.fail_on_variance.is_some()
+        || opts.format != Format::Table
+        || opts.precision != 2
+        || opts.allow_debug
+        || opts.check_only
+    {
+        return Err(
+            "run-file, isolate, shuffle, suite-repeats, cooldown, output, output-dir, dump-samples, baseline, sqlite, \
+             set-baseline, reference-factor, calibrate-reference, color, progress, relative-to, tag, fail-on-variance, \
+             format, precision, allow-debug and check-only apply to the whole campaign"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+/// Nearest-rank percentile (`p` in 0..=100) of an ascending-sorted slice.
+pub fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Coefficient of variation (stddev / mean) in percent; 0 for fewer than two
+/// values.
+pub fn cv_percent(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    variance.sqrt() / mean * 100.0
+}
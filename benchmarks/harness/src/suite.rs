@@ -0,0 +1,561 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::{Args, Command};
+use serde::{Deserialize, Serialize};
+
+use crate::history::History;
+use crate::outdir::OutputDir;
+use crate::report::load_baseline;
+use crate::{color, isolate, progress, runfile};
+use crate::rng::XorShift64;
+use crate::stats::cv_percent;
+use crate::{reference_factor, spread_percent, Format, Measurement, Quality, Report, SampleDump, SuiteOptions};
+
+type BenchFn<'a> = Box<dyn FnMut() -> Result<Measurement, String> + 'a>;
+
+struct Registered<'a> {
+    category: String,
+    name: String,
+    f: BenchFn<'a>,
+}
+
+/// Runs a list of named benchmarks, isolating each one so a failure or panic
+/// is recorded and the rest of the suite still runs.
+///
+/// Benchmarks run in registration order, which each binary keeps fixed so
+/// runs are reproducible. `--shuffle` randomizes the order to expose
+/// order-dependent effects (thermal state, cache warmth); if results move
+/// noticeably under shuffling, the measurements are contaminated.
+pub struct Suite<'a> {
+    opts: &'a SuiteOptions,
+    benches: Vec<Registered<'a>>,
+    command: Option<Command>,
+}
+
+/// Outcome of one benchmark in a [`Suite`].
+#[derive(Debug, Clone)]
+pub struct BenchmarkResult {
+    /// Group the benchmark is reported under (`regex`, `json`, `cpu`, ...).
+    pub category: String,
+    pub name: String,
+    pub outcome: Result<Measurement, Failure>,
+    /// Trust grade of a successful measurement; `None` for failures.
+    pub quality: Option<Quality>,
+    /// Mean time per iteration in each successful `--suite-repeats` pass;
+    /// empty for a single pass.
+    pub suite_runs: Vec<Duration>,
+    /// Mean time per iteration in the `--baseline` report, or without one the
+    /// latest `--set-baseline` run in the `--sqlite` database.
+    pub baseline: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Failure {
+    /// The benchmark reported an error (bad pattern, failed check).
+    Error(String),
+    /// The benchmark panicked; holds the panic message.
+    Panic(String),
+}
+
+impl<'a> Suite<'a> {
+    pub fn new(opts: &'a SuiteOptions) -> Self {
+        Self {
+            opts,
+            benches: Vec::new(),
+            command: None,
+        }
+    }
+
+    /// The binary's full clap command (`Args::command()`), which `--run-file`
+    /// params are checked against so a run can set the binary's own flags as
+    /// well as the harness ones. Without it only harness flags are accepted.
+    pub fn command(mut self, command: Command) -> Self {
+        self.command = Some(command);
+        self
+    }
+
+    /// Registers a benchmark under `category`. It runs when [`Suite::run`] is called.
+    pub fn add<F>(&mut self, category: &str, name: &str, f: F)
+    where
+        F: FnMut() -> Result<Measurement, String> + 'a,
+    {
+        self.benches.push(Registered {
+            category: category.to_string(),
+            name: name.to_string(),
+            f: Box::new(f),
+        });
+    }
+
+    /// Runs every benchmark, in registration order unless `--shuffle` is set,
+    /// feeding `--dump-samples` and the `--format` report as each finishes.
+    /// Results come back in the order the benchmarks ran.
+    ///
+    /// Under `--color` the table rows are held and printed, marked, once the
+    /// last benchmark finishes. Under `--check-only` they are dropped and each
+    /// benchmark gets a pass/fail line instead. `--progress` draws its bar on stderr for the
+    /// length of the run.
+    ///
+    /// Under `--run-file` the campaign's runs replace the registered order;
+    /// an invalid run file is reported in full and exits before anything runs.
+    pub fn run(mut self) -> Vec<BenchmarkResult> {
+        if let Some(only) = &self.opts.isolated_child {
+            let bench = self.benches.iter_mut().find(|b| &b.name == only);
+            isolate::run_as_child(|| match bench {
+                Some(bench) => run_in_process(&mut bench.f),
+                None => Err(Failure::Error(format!("no benchmark named `{}`", only))),
+            });
+        }
+
+        let opts = self.opts;
+        let mut jobs = self.plan();
+        if let Some(reference) = &opts.relative_to {
+            if !jobs.iter().any(|job| &job.name == reference) {
+                let names: Vec<&str> = jobs.iter().map(|job| job.name.as_str()).collect();
+                eprintln!("--relative-to: no benchmark named `{}` (have: {})", reference, names.join(", "));
+                process::exit(2);
+            }
+        }
+        if opts.shuffle {
+            XorShift64::new(shuffle_seed(opts)).shuffle(&mut jobs);
+        }
+
+        let mut dump = SampleDump::create(opts).expect("Failed to create sample dump");
+        // Under --check-only there are no timings to report
+        let mut report = if opts.check_only {
+            None
+        } else {
+            Report::create(opts).expect("Failed to create report")
+        };
+        let mut output_dir = OutputDir::create(opts).expect("Failed to create --output-dir");
+        let history = opts.sqlite.as_deref().map(History::open).transpose().expect("Failed to open --sqlite database");
+        let baseline = self::baseline(opts, history.as_ref());
+        if let (Some(report), Some(baseline)) = (report.as_mut(), &baseline) {
+            report.default_baseline(baseline);
+        }
+        let color = color::enabled(opts);
+        let mut held = vec![Vec::new(); jobs.len()];
+        let passes = opts.suite_repeats;
+        let mut outcomes: Vec<Option<Result<Measurement, Failure>>> = (0..jobs.len()).map(|_| None).collect();
+        let mut suite_runs = vec![Vec::new(); jobs.len()];
+        let mut results = Vec::with_capacity(jobs.len());
+        // Calibrating first keeps it clear of heat left by the benchmarks
+        if !opts.check_only {
+            reference_factor(opts);
+        }
+        progress::start(opts, jobs.len() * passes);
+
+        // Under --suite-repeats every pass runs the whole list again; results
+        // are merged and reported as the last pass finishes each benchmark
+        for pass in 1..=passes {
+            if passes > 1 && opts.format == Format::Table && !color {
+                progress::suspend(|| println!("Suite run {} of {}", pass, passes));
+            }
+            for (i, job) in jobs.iter_mut().enumerate() {
+                // --cooldown: a pause before every benchmark but the first
+                if let Some(cooldown) = opts.cooldown.filter(|_| pass > 1 || i > 0) {
+                    progress::running("cooling down");
+                    thread::sleep(cooldown);
+                }
+                if passes > 1 {
+                    progress::running(&format!("{} (run {} of {})", job.name, pass, passes));
+                } else {
+                    progress::running(&job.name);
+                }
+                // --check-only holds the rows too, and drops them
+                if color || opts.check_only {
+                    color::hold();
+                }
+                let outcome = match &mut job.kind {
+                    JobKind::InProcess(f) => run_in_process(f),
+                    JobKind::Child { benchmark, args } => isolate::run_child(benchmark, args),
+                };
+                progress::advance();
+                if color {
+                    held[i].extend(color::release());
+                } else if opts.check_only {
+                    color::release();
+                }
+                if let Ok(measurement) = &outcome {
+                    suite_runs[i].push(measurement.per_iter());
+                }
+                outcomes[i] = Some(match (outcomes[i].take(), outcome) {
+                    (None, outcome) | (Some(Ok(_)), outcome @ Err(_)) => outcome,
+                    (Some(Ok(mut merged)), Ok(measurement)) => {
+                        merged.absorb(measurement);
+                        Ok(merged)
+                    }
+                    (Some(failed @ Err(_)), _) => failed,
+                });
+                if pass < passes {
+                    continue;
+                }
+
+                let outcome = outcomes[i].take().expect("every job ran this pass");
+                if let (Some(dump), Ok(measurement)) = (dump.as_mut(), &outcome) {
+                    dump.write(&job.name, measurement).expect("Failed to write samples");
+                }
+                let quality = if opts.check_only {
+                    None
+                } else {
+                    outcome.as_ref().ok().map(|m| Quality::assess(m, opts))
+                };
+                let result = BenchmarkResult {
+                    category: job.category.clone(),
+                    name: job.name.clone(),
+                    outcome,
+                    quality,
+                    suite_runs: if passes > 1 { std::mem::take(&mut suite_runs[i]) } else { Vec::new() },
+                    baseline: baseline
+                        .as_ref()
+                        .and_then(|b| b.get(&job.name))
+                        .map(|ns| Duration::from_secs_f64(ns / 1e9)),
+                };
+                if let Some(report) = report.as_mut() {
+                    report.record(&result).expect("Failed to write report");
+                }
+                if let Some(output_dir) = output_dir.as_mut() {
+                    output_dir.record(&result).expect("Failed to write --output-dir file");
+                }
+                results.push(result);
+            }
+        }
+
+        progress::finish();
+        if opts.check_only {
+            enforce_checks(&results);
+        }
+        if color {
+            color::print_held(&held, &results);
+        }
+        if let Some(report) = report {
+            report.finish(&results).expect("Failed to write report");
+        }
+        if let Some(output_dir) = output_dir {
+            output_dir.finish(&results).expect("Failed to write --output-dir summary");
+        }
+        if let Some(history) = history {
+            history.record(opts, &results).expect("Failed to write --sqlite database");
+        }
+        // A table run gates after print_summary, once the failures are listed
+        if opts.format != Format::Table {
+            enforce_variance_gate(opts, &results);
+        }
+
+        results
+    }
+
+    // What to run and how: in this process, one child per benchmark under
+    // --isolate, or one child per campaign entry under --run-file
+    fn plan(self) -> Vec<Job<'a>> {
+        let Some(path) = &self.opts.run_file else {
+            let isolate = self.opts.isolate.then(isolate::parent_args);
+            return self
+                .benches
+                .into_iter()
+                .map(|b| Job {
+                    kind: match &isolate {
+                        Some(args) => JobKind::Child {
+                            benchmark: b.name.clone(),
+                            args: args.clone(),
+                        },
+                        None => JobKind::InProcess(b.f),
+                    },
+                    category: b.category,
+                    name: b.name,
+                })
+                .collect();
+        };
+
+        let known: Vec<&str> = self.benches.iter().map(|b| b.name.as_str()).collect();
+        let command = self.command.unwrap_or_else(|| SuiteOptions::augment_args(Command::new("run")));
+        let runs = runfile::load(path, &known, command).unwrap_or_else(|errors| {
+            eprintln!("Invalid run file {}:", path.display());
+            for error in errors {
+                eprintln!("  {}", error);
+            }
+            process::exit(2);
+        });
+        runs.into_iter()
+            .map(|run| Job {
+                category: self
+                    .benches
+                    .iter()
+                    .find(|b| b.name == run.benchmark)
+                    .map(|b| b.category.clone())
+                    .unwrap_or_default(),
+                name: run.label,
+                kind: JobKind::Child {
+                    benchmark: run.benchmark,
+                    args: run.args,
+                },
+            })
+            .collect()
+    }
+}
+
+// Mean ns per iteration to compare against, by benchmark name: the --baseline
+// file, else the latest --set-baseline run for this binary in --sqlite
+fn baseline(opts: &SuiteOptions, history: Option<&History>) -> Option<HashMap<String, f64>> {
+    if let Some(path) = &opts.baseline {
+        return Some(load_baseline(path).expect("Failed to read --baseline"));
+    }
+    let (history, path) = (history?, opts.sqlite.as_deref()?);
+    let stored = history.latest_baseline().expect("Failed to read baseline from --sqlite database")?;
+    let commit = stored.git_commit.as_deref().map_or("unknown commit", |c| &c[..c.len().min(12)]);
+    eprintln!("Baseline: run {} ({}) from {}", stored.run_id, commit, path.display());
+    Some(stored.avg_ns)
+}
+
+struct Job<'a> {
+    category: String,
+    name: String,
+    kind: JobKind<'a>,
+}
+
+enum JobKind<'a> {
+    InProcess(BenchFn<'a>),
+    Child { benchmark: String, args: Vec<String> },
+}
+
+fn run_in_process(f: &mut BenchFn) -> Result<Measurement, Failure> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(measurement)) => Ok(measurement),
+        Ok(Err(message)) => Err(Failure::Error(message)),
+        Err(payload) => Err(Failure::Panic(panic_message(payload))),
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Groups results by category, categories in order of first appearance and
+/// results in run order within each.
+pub fn by_category(results: &[BenchmarkResult]) -> Vec<(&str, Vec<&BenchmarkResult>)> {
+    let mut groups: Vec<(&str, Vec<&BenchmarkResult>)> = Vec::new();
+    for result in results {
+        match groups.iter_mut().find(|(category, _)| *category == result.category) {
+            Some((_, group)) => group.push(result),
+            None => groups.push((&result.category, vec![result])),
+        }
+    }
+    groups
+}
+
+/// Seed used for `--shuffle`: the explicit `--seed`, or one taken from the clock.
+/// Resolved once per process so the reported seed matches the order used.
+fn shuffle_seed(opts: &SuiteOptions) -> u64 {
+    static CLOCK_SEED: std::sync::OnceLock<u64> = std::sync::OnceLock::new();
+    opts.seed.unwrap_or_else(|| {
+        *CLOCK_SEED.get_or_init(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(1)
+        })
+    })
+}
+
+/// Prints the run order when shuffled, the `--cooldown` pauses, per-category
+/// subtotals when the suite spans more than one category, the spread across
+/// `--suite-repeats` passes, every result relative to the `--relative-to`
+/// benchmark, the change from the baseline run when there is one, times
+/// normalized to the reference machine under `--reference-factor` or
+/// `--calibrate-reference`, then lists low-quality results and the
+/// benchmarks that failed or panicked. Under `--fail-on-variance` it then
+/// exits with status 3 if any result is too noisy.
+pub fn print_summary(opts: &SuiteOptions, results: &[BenchmarkResult]) {
+    if opts.shuffle {
+        let order: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        println!("Order (shuffled, --seed {}): {}", shuffle_seed(opts), order.join(", "));
+    }
+
+    let groups = by_category(results);
+    let p = opts.precision;
+    if let Some(cooldown) = opts.cooldown {
+        let pauses = (results.len() * opts.suite_repeats).saturating_sub(1);
+        println!(
+            "Cooldown: {:?} between benchmarks ({} pauses, {:.p$} s total)",
+            cooldown,
+            pauses,
+            cooldown.as_secs_f64() * pauses as f64
+        );
+    }
+    if groups.len() > 1 {
+        for (category, group) in &groups {
+            let ok: Vec<_> = group.iter().filter_map(|r| r.outcome.as_ref().ok()).collect();
+            let total_ms: f64 = ok.iter().map(|m| m.total().as_secs_f64() * 1000.0).sum();
+            println!(
+                "  {:<20} {} of {} ok, {:.p$} ms total",
+                category,
+                ok.len(),
+                group.len(),
+                total_ms
+            );
+        }
+    }
+
+    if opts.suite_repeats > 1 {
+        println!("Across {} suite runs:", opts.suite_repeats);
+        for r in results.iter().filter(|r| !r.suite_runs.is_empty()) {
+            let us: Vec<f64> = r.suite_runs.iter().map(|d| d.as_secs_f64() * 1_000_000.0).collect();
+            let mean = us.iter().sum::<f64>() / us.len() as f64;
+            println!(
+                "  {:<20} mean {:.p$} µs, cross-run CV {:.p$}% ({} runs)",
+                r.name,
+                mean,
+                cv_percent(&us),
+                us.len()
+            );
+        }
+    }
+
+    if let Some(reference) = &opts.relative_to {
+        print_relative(reference, results, p);
+    }
+
+    if results.iter().any(|r| r.baseline.is_some()) {
+        println!("Against baseline:");
+        for r in results {
+            let (Ok(m), Some(base)) = (&r.outcome, r.baseline) else {
+                continue;
+            };
+            let (now, then) = (m.per_iter().as_secs_f64(), base.as_secs_f64());
+            println!(
+                "  {:<20} {:.p$} µs -> {:.p$} µs ({:+.p$}%)",
+                r.name,
+                then * 1_000_000.0,
+                now * 1_000_000.0,
+                (now / then - 1.0) * 100.0
+            );
+        }
+    }
+
+    if let Some(factor) = reference_factor(opts) {
+        println!("Normalized to the reference machine (factor {:.3}):", factor);
+        for r in results {
+            if let Ok(m) = &r.outcome {
+                let us = m.per_iter().as_secs_f64() * 1_000_000.0;
+                println!("  {:<20} {:.p$} µs here, ~{:.p$} µs on the reference", r.name, us, us * factor);
+            }
+        }
+    }
+
+    let low: Vec<_> = results
+        .iter()
+        .filter(|r| r.quality == Some(Quality::Low))
+        .filter_map(|r| r.outcome.as_ref().ok().map(|m| (&r.name, m)))
+        .collect();
+    if !low.is_empty() {
+        println!("LOW QUALITY - do not cite these numbers: {} of {}", low.len(), results.len());
+        for (name, m) in low {
+            let samples: usize = m.samples.iter().map(Vec::len).sum();
+            println!("  {:<20} spread {:.p$}%, {} samples", name, spread_percent(m), samples);
+        }
+    }
+
+    let failed: Vec<_> = results
+        .iter()
+        .filter_map(|r| r.outcome.as_ref().err().map(|e| (&r.name, e)))
+        .collect();
+    if !failed.is_empty() {
+        println!("Failed benchmarks: {} of {}", failed.len(), results.len());
+        for (name, failure) in failed {
+            match failure {
+                Failure::Error(message) => println!("  {:<20} error: {}", name, message),
+                Failure::Panic(message) => println!("  {:<20} panicked: {}", name, message),
+            }
+        }
+    }
+
+    enforce_variance_gate(opts, results);
+}
+
+/// `--check-only`: prints one line per benchmark and exits with status 1 if
+/// any failed its checks or panicked.
+fn enforce_checks(results: &[BenchmarkResult]) {
+    for r in results {
+        match &r.outcome {
+            Ok(_) => println!("check {:<20} ok", r.name),
+            Err(Failure::Error(message)) => println!("check {:<20} FAILED: {}", r.name, message),
+            Err(Failure::Panic(message)) => println!("check {:<20} FAILED (panicked): {}", r.name, message),
+        }
+    }
+    let failed = results.iter().filter(|r| r.outcome.is_err()).count();
+    println!("Checks: {} of {} passed", results.len() - failed, results.len());
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// `--fail-on-variance`: exits with status 3 if any successful result's
+/// spread is above the threshold, listing the offenders on stderr. Exit
+/// status 2 stays reserved for usage errors and debug builds.
+fn enforce_variance_gate(opts: &SuiteOptions, results: &[BenchmarkResult]) {
+    let Some(threshold) = opts.fail_on_variance else {
+        return;
+    };
+    let noisy: Vec<_> = results
+        .iter()
+        .filter_map(|r| r.outcome.as_ref().ok().map(|m| (&r.name, spread_percent(m))))
+        .filter(|(_, spread)| *spread > threshold)
+        .collect();
+    if noisy.is_empty() {
+        return;
+    }
+    let p = opts.precision;
+    eprintln!(
+        "--fail-on-variance: {} of {} results above {}% spread; this machine is too noisy to trust",
+        noisy.len(),
+        results.len(),
+        threshold
+    );
+    for (name, spread) in noisy {
+        eprintln!("  {:<20} spread {:.p$}%", name, spread);
+    }
+    process::exit(3);
+}
+
+// Each successful result as a multiple of the reference benchmark's mean time
+fn print_relative(reference: &str, results: &[BenchmarkResult], p: usize) {
+    let Some(base) = results
+        .iter()
+        .find(|r| r.name == reference)
+        .and_then(|r| r.outcome.as_ref().ok())
+    else {
+        println!("Relative to {}: reference benchmark failed", reference);
+        return;
+    };
+    let base_secs = base.per_iter().as_secs_f64();
+    println!(
+        "Relative to {} (1x = {:.p$} µs):",
+        reference,
+        base_secs * 1_000_000.0
+    );
+    for r in results {
+        if let Ok(m) = &r.outcome {
+            println!("  {:<20} {}x", r.name, format_ratio(m.per_iter().as_secs_f64() / base_secs, p));
+        }
+    }
+}
+
+// `p` decimal places, or more when that would round a small ratio to zero
+fn format_ratio(ratio: f64, p: usize) -> String {
+    let decimals = if ratio > 0.0 && ratio < 1.0 {
+        p.max(-ratio.log10().floor() as usize + 1)
+    } else {
+        p
+    };
+    format!("{:.*}", decimals, ratio)
+}
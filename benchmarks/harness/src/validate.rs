@@ -0,0 +1,45 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Clap value parser that accepts a `T` within `min..=max`.
+///
+/// Every numeric flag goes through this so that out-of-range input fails at
+/// parse time with `must be between A and B, got Y` instead of hanging,
+/// overflowing or panicking partway through a run.
+pub fn in_range<T>(min: T, max: T) -> impl Fn(&str) -> Result<T, String> + Clone + Send + Sync + 'static
+where
+    T: FromStr + PartialOrd + Display + Copy + Send + Sync + 'static,
+{
+    move |s: &str| {
+        let value: T = s
+            .trim()
+            .parse()
+            .map_err(|_| format!("must be a number between {} and {}, got `{}`", min, max, s))?;
+        check_range(value, min, max)
+    }
+}
+
+/// Checks an already-parsed value with the same message as [`in_range`].
+/// NaN is outside every range, so float flags reject it along with infinity.
+pub fn check_range<T: PartialOrd + Display>(value: T, min: T, max: T) -> Result<T, String> {
+    if !(&min..=&max).contains(&&value) {
+        return Err(format!("must be between {} and {}, got {}", min, max, value));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_nan_and_infinity() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(check_range(value, 0.001, 100.0).is_err(), "{} accepted", value);
+        }
+        for s in ["NaN", "inf", "-inf"] {
+            assert!(in_range(1.0, 1e9)(s).is_err(), "{} accepted", s);
+        }
+        assert_eq!(check_range(100.0, 0.001, 100.0), Ok(100.0));
+    }
+}
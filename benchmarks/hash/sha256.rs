@@ -0,0 +1,83 @@
+// Hash Throughput Benchmark: one-shot digest of a large buffer
+// Single-threaded MB/s, for comparison with the hashing throughput other
+// languages report; bench_cpu uses SHA256 for parallel scaling instead
+use clap::{Parser, ValueEnum};
+use harness::{in_range, Bencher, Options};
+use sha2::{Digest, Sha256, Sha512};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 50;
+
+#[derive(ValueEnum, Clone, Copy)]
+enum Algorithm {
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl Algorithm {
+    fn title(self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "SHA256",
+            Algorithm::Sha512 => "SHA512",
+            Algorithm::Blake3 => "BLAKE3",
+        }
+    }
+
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Algorithm::Sha256 => Sha256::digest(data).to_vec(),
+            Algorithm::Sha512 => Sha512::digest(data).to_vec(),
+            Algorithm::Blake3 => blake3::hash(data).as_bytes().to_vec(),
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(about = "Hash throughput benchmark")]
+struct Args {
+    /// Digest to time
+    #[arg(long, value_enum, default_value_t = Algorithm::Sha256)]
+    digest: Algorithm,
+
+    /// Buffer size in MiB
+    #[arg(long, value_name = "MIB", default_value_t = 64, value_parser = in_range(1usize, 4096))]
+    size: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// Deterministic pseudo-random bytes (xorshift64), so every run hashes the same input
+fn generate(len: usize) -> Vec<u8> {
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    let mut buf = Vec::with_capacity(len + 8);
+    while buf.len() < len {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        buf.extend_from_slice(&state.to_le_bytes());
+    }
+    buf.truncate(len);
+    buf
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let data = generate(args.size << 20);
+    let digest = args.digest.digest(&data);
+
+    let m = Bencher::new(opts, ITERATIONS).warmup(2).run(|| {
+        black_box(args.digest.digest(black_box(&data)));
+    });
+
+    let p = opts.precision;
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    println!("Benchmark: {} throughput", args.digest.title());
+    println!("Buffer: {} bytes", data.len());
+    println!("Digest: {}", hex);
+    println!("Time: {:.p$}ms/hash", m.per_iter().as_secs_f64() * 1000.0);
+    println!("Throughput: {:.p$} MB/s", data.len() as f64 / 1_000_000.0 * m.ops_per_sec());
+    m.print_notes("Throughput", p);
+}
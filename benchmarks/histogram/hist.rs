@@ -0,0 +1,110 @@
+// Histogram Benchmark: bin a large f64 stream into K equal-width buckets,
+// sequentially or as per-thread rayon histograms merged at the end
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Options};
+use rayon::prelude::*;
+use std::hint::black_box;
+
+const ITERATIONS: usize = 20;
+// Values are drawn from [0, RANGE)
+const RANGE: f64 = 1000.0;
+
+#[derive(Parser)]
+#[command(about = "Streaming histogram benchmark")]
+struct Args {
+    /// Number of f64 elements
+    #[arg(long, default_value_t = 10_000_000, value_parser = in_range(1usize, 1_000_000_000))]
+    n: usize,
+
+    /// Number of equal-width buckets
+    #[arg(long, default_value_t = 64, value_parser = in_range(1usize, 1_000_000))]
+    buckets: usize,
+
+    /// Build per-thread histograms on rayon and merge them
+    #[arg(long)]
+    parallel: bool,
+
+    /// Seed for the generated data
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+struct Binner {
+    buckets: usize,
+    scale: f64,
+}
+
+impl Binner {
+    fn new(buckets: usize) -> Self {
+        Self {
+            buckets,
+            scale: buckets as f64 / RANGE,
+        }
+    }
+
+    // The top edge is exclusive, but rounding can still land on `buckets`
+    fn bucket(&self, x: f64) -> usize {
+        ((x * self.scale) as usize).min(self.buckets - 1)
+    }
+
+    fn add(&self, mut counts: Vec<u64>, x: f64) -> Vec<u64> {
+        counts[self.bucket(x)] += 1;
+        counts
+    }
+}
+
+fn merge(mut a: Vec<u64>, b: Vec<u64>) -> Vec<u64> {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a += b;
+    }
+    a
+}
+
+fn sequential(data: &[f64], binner: &Binner) -> Vec<u64> {
+    data.iter().fold(vec![0; binner.buckets], |counts, &x| binner.add(counts, x))
+}
+
+fn parallel(data: &[f64], binner: &Binner) -> Vec<u64> {
+    data.par_iter()
+        .fold(|| vec![0; binner.buckets], |counts, &x| binner.add(counts, x))
+        .reduce(|| vec![0; binner.buckets], merge)
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let binner = Binner::new(args.buckets);
+
+    // Sum of two uniforms, so the buckets are unevenly filled (a triangle)
+    let mut rng = XorShift64::new(args.seed);
+    let data: Vec<f64> = (0..args.n)
+        .map(|_| (rng.next_f64() + rng.next_f64()) * RANGE / 2.0)
+        .collect();
+
+    // Every element lands in exactly one bucket, and merging per-thread
+    // histograms must give the same counts as one pass
+    let counts = sequential(&data, &binner);
+    assert_eq!(counts.iter().sum::<u64>(), args.n as u64, "Bucket counts do not sum to the element count");
+    if args.parallel {
+        assert_eq!(parallel(&data, &binner), counts, "Merged histogram differs from the sequential one");
+    }
+
+    let m = Bencher::new(opts, ITERATIONS).warmup(2).run(|| {
+        let data = black_box(&data[..]);
+        black_box(if args.parallel { parallel(data, &binner) } else { sequential(data, &binner) });
+    });
+
+    let p = opts.precision;
+    let mode = if args.parallel { "rayon, merged per-thread histograms" } else { "sequential" };
+    let fullest = counts.iter().max().copied().unwrap_or(0);
+    println!("Benchmark: Histogram ({})", mode);
+    println!("Elements: {}", args.n);
+    println!("Buckets: {} (fullest holds {:.p$}%)", args.buckets, fullest as f64 / args.n as f64 * 100.0);
+    println!("Time: {:.p$}ms/pass", m.per_iter().as_secs_f64() * 1000.0);
+    println!("Elements/sec: {:.0}", args.n as f64 * m.ops_per_sec());
+    m.print_notes("Time", p);
+}
@@ -0,0 +1,73 @@
+// Iterator Chain Benchmark: manual for loop vs .filter().map().fold()
+// Both sum the squares of the odd numbers below N that are not multiples of
+// 3; if iterator adapters are zero-cost the two should time the same
+use clap::Parser;
+use harness::{in_range, Bencher, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 1000;
+// Within 5% of each other counts as "same speed"
+const EQUAL_TOLERANCE: f64 = 0.05;
+
+#[derive(Parser)]
+#[command(about = "Manual loop vs iterator chain benchmark")]
+struct Args {
+    /// Upper bound of the range
+    #[arg(long, default_value_t = 1_000_000, value_parser = in_range(1u64, 1 << 32))]
+    n: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn manual_loop(n: u64) -> u64 {
+    let mut acc = 0u64;
+    for i in 0..n {
+        if i % 2 == 1 && i % 3 != 0 {
+            acc = acc.wrapping_add(i * i);
+        }
+    }
+    acc
+}
+
+fn iterator_chain(n: u64) -> u64 {
+    (0..n)
+        .filter(|i| i % 2 == 1)
+        .filter(|i| i % 3 != 0)
+        .map(|i| i * i)
+        .fold(0u64, |acc, x| acc.wrapping_add(x))
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+
+    assert_eq!(manual_loop(args.n), iterator_chain(args.n), "Loop and chain results differ");
+
+    let manual = Bencher::new(opts, ITERATIONS).warmup(20).run(|| {
+        black_box(manual_loop(black_box(args.n)));
+    });
+    let chain = Bencher::new(opts, ITERATIONS).warmup(20).run(|| {
+        black_box(iterator_chain(black_box(args.n)));
+    });
+
+    let p = opts.precision;
+    let manual_us = manual.per_iter().as_secs_f64() * 1_000_000.0;
+    let chain_us = chain.per_iter().as_secs_f64() * 1_000_000.0;
+    let ratio = chain_us / manual_us;
+    let verdict = if (ratio - 1.0).abs() <= EQUAL_TOLERANCE {
+        "same speed"
+    } else if ratio > 1.0 {
+        "chain slower"
+    } else {
+        "chain faster"
+    };
+
+    println!("Benchmark: Iterator chain overhead (N = {})", args.n);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Manual for loop:        {:.p$}µs/iter", manual_us);
+    println!(".filter().map().fold(): {:.p$}µs/iter", chain_us);
+    println!("Chain / loop: {:.p$}x ({})", ratio, verdict);
+    manual.print_notes("Manual for loop", p);
+    chain.print_notes(".filter().map().fold()", p);
+}
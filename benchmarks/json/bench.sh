@@ -91,42 +91,8 @@ while i < 100000:
     i = i + 1
 EOF
 
-# Rust source
-mkdir -p rust/src
-cat > rust/Cargo.toml <<'EOF'
-[package]
-name = "json_bench"
-version = "0.1.0"
-edition = "2021"
-
-[[bin]]
-name = "parse"
-path = "src/parse.rs"
-
-[[bin]]
-name = "stringify"
-path = "src/stringify.rs"
-
-[dependencies]
-serde_json = "1.0"
-EOF
-
-cat > rust/src/parse.rs <<'EOF'
-use std::fs;
-fn main() {
-    let data = fs::read_to_string("sample.json").unwrap();
-    for _ in 0..50_000 { let _: serde_json::Value = serde_json::from_str(&data).unwrap(); }
-}
-EOF
-
-cat > rust/src/stringify.rs <<'EOF'
-use std::fs;
-fn main() {
-    let data = fs::read_to_string("sample.json").unwrap();
-    let parsed: serde_json::Value = serde_json::from_str(&data).unwrap();
-    for _ in 0..100_000 { let _ = serde_json::to_string(&parsed).unwrap(); }
-}
-EOF
+# Rust source lives in rust/ (driven by the shared harness in ../harness,
+# so it is no longer regenerated here)
 
 # Go source
 mkdir -p go
@@ -0,0 +1,25 @@
+use clap::Parser;
+use harness::{Bencher, Options};
+use std::fs;
+
+#[derive(Parser)]
+#[command(about = "serde_json parse benchmark")]
+struct Args {
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn main() {
+    let args = Args::parse();
+    let data = fs::read_to_string("sample.json").unwrap();
+    // --steady-state pre-parses so Value allocations come from warm free lists
+    let m = Bencher::new(&args.harness, 50_000).steady_state_warmup(500).run(|| {
+        let _: serde_json::Value = serde_json::from_str(&data).unwrap();
+    });
+    let p = args.harness.precision;
+    println!("parse: {} ops, {:.p$} ops/sec", m.iterations(), m.ops_per_sec());
+    if let Some(latency) = m.latency_summary(p) {
+        println!("parse: {}", latency);
+    }
+    m.print_notes("parse", p);
+}
@@ -0,0 +1,212 @@
+use clap::Parser;
+use harness::{Bencher, Options};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fs;
+
+#[derive(Parser)]
+#[command(about = "serde_json owned vs zero-copy struct deserialization benchmark")]
+struct Args {
+    #[command(flatten)]
+    harness: Options,
+}
+
+const ITERATIONS: usize = 50_000;
+
+// sample.json as typed structs with every string owned
+mod owned {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Sample {
+        pub metadata: Metadata,
+        pub users: Vec<User>,
+        pub products: Vec<Product>,
+        pub analytics: Analytics,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Metadata {
+        pub version: String,
+        pub timestamp: String,
+        pub source: String,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct User {
+        pub id: u64,
+        pub name: String,
+        pub email: String,
+        pub active: bool,
+        pub score: f64,
+        pub tags: Vec<String>,
+        pub profile: Profile,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Profile {
+        pub bio: String,
+        pub settings: Settings,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Settings {
+        pub notifications: bool,
+        pub theme: String,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Product {
+        pub sku: String,
+        pub name: String,
+        pub description: String,
+        pub price: f64,
+        #[serde(rename = "inStock")]
+        pub in_stock: bool,
+        pub reviews: Vec<Review>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Review {
+        pub rating: f64,
+        pub reviewer: String,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Analytics {
+        pub daily_stats: Vec<DailyStat>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct DailyStat {
+        pub date: String,
+        pub visits: u64,
+        pub revenue: f64,
+    }
+}
+
+// The same structs borrowing their strings from the input. A &str can only
+// borrow a string with no escapes, so the two fields that hold escapes
+// (bio, description) are Cow: borrowed where possible, allocated otherwise.
+mod borrowed {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Sample<'a> {
+        #[serde(borrow)]
+        pub metadata: Metadata<'a>,
+        #[serde(borrow)]
+        pub users: Vec<User<'a>>,
+        #[serde(borrow)]
+        pub products: Vec<Product<'a>>,
+        #[serde(borrow)]
+        pub analytics: Analytics<'a>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Metadata<'a> {
+        pub version: &'a str,
+        pub timestamp: &'a str,
+        pub source: &'a str,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct User<'a> {
+        pub id: u64,
+        pub name: &'a str,
+        pub email: &'a str,
+        pub active: bool,
+        pub score: f64,
+        #[serde(borrow)]
+        pub tags: Vec<&'a str>,
+        #[serde(borrow)]
+        pub profile: Profile<'a>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Profile<'a> {
+        #[serde(borrow)]
+        pub bio: Cow<'a, str>,
+        #[serde(borrow)]
+        pub settings: Settings<'a>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Settings<'a> {
+        pub notifications: bool,
+        pub theme: &'a str,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Product<'a> {
+        pub sku: &'a str,
+        pub name: &'a str,
+        #[serde(borrow)]
+        pub description: Cow<'a, str>,
+        pub price: f64,
+        #[serde(rename = "inStock")]
+        pub in_stock: bool,
+        #[serde(borrow)]
+        pub reviews: Vec<Review<'a>>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Review<'a> {
+        pub rating: f64,
+        pub reviewer: &'a str,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Analytics<'a> {
+        #[serde(borrow)]
+        pub daily_stats: Vec<DailyStat<'a>>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct DailyStat<'a> {
+        pub date: &'a str,
+        pub visits: u64,
+        pub revenue: f64,
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let data = fs::read_to_string("sample.json").unwrap();
+
+    // Both decode everything in the file, and agree
+    let expected: serde_json::Value = serde_json::from_str(&data).unwrap();
+    let owned: owned::Sample = serde_json::from_str(&data).unwrap();
+    let borrowed: borrowed::Sample = serde_json::from_str(&data).unwrap();
+    assert_eq!(serde_json::to_value(&owned).unwrap(), expected, "Owned structs lost data");
+    assert_eq!(serde_json::to_value(&borrowed).unwrap(), expected, "Borrowed structs lost data");
+    let escaped = borrowed.users.iter().filter(|u| matches!(u.profile.bio, Cow::Owned(_))).count()
+        + borrowed.products.iter().filter(|p| matches!(p.description, Cow::Owned(_))).count();
+
+    let owned_m = Bencher::new(opts, ITERATIONS).steady_state_warmup(500).run(|| {
+        let _: owned::Sample = serde_json::from_str(&data).unwrap();
+    });
+    let borrowed_m = Bencher::new(opts, ITERATIONS).steady_state_warmup(500).run(|| {
+        let _: borrowed::Sample = serde_json::from_str(&data).unwrap();
+    });
+
+    let p = opts.precision;
+    let mb_per_sec = |ops_per_sec: f64| data.len() as f64 * ops_per_sec / 1e6;
+    println!(
+        "parse (owned String):    {} ops, {:.p$} ops/sec, {:.p$} MB/s",
+        owned_m.iterations(),
+        owned_m.ops_per_sec(),
+        mb_per_sec(owned_m.ops_per_sec())
+    );
+    println!(
+        "parse (borrowed &str):   {} ops, {:.p$} ops/sec, {:.p$} MB/s ({} escaped strings allocated)",
+        borrowed_m.iterations(),
+        borrowed_m.ops_per_sec(),
+        mb_per_sec(borrowed_m.ops_per_sec()),
+        escaped
+    );
+    println!("Zero-copy vs owned: {:.p$}x", borrowed_m.ops_per_sec() / owned_m.ops_per_sec());
+    owned_m.print_notes("parse (owned String)", p);
+    borrowed_m.print_notes("parse (borrowed &str)", p);
+}
@@ -0,0 +1,70 @@
+use clap::Parser;
+use harness::{Bencher, Options};
+use std::fs;
+
+#[derive(Parser)]
+#[command(about = "serde_json stringify benchmark")]
+struct Args {
+    #[command(flatten)]
+    harness: Options,
+
+    /// Compare to_string, to_vec and to_writer into one reused Vec<u8>
+    #[arg(long)]
+    targets: bool,
+}
+
+const ITERATIONS: usize = 100_000;
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let data = fs::read_to_string("sample.json").unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&data).unwrap();
+    // --steady-state pre-runs full serializations so the allocator already holds
+    // output-sized buffers before the measured loop starts
+    let bencher = || Bencher::new(opts, ITERATIONS).steady_state_warmup(1_000);
+    let p = opts.precision;
+
+    if !args.targets {
+        let m = bencher().run(|| {
+            let _ = serde_json::to_string(&parsed).unwrap();
+        });
+        println!("stringify: {} ops, {:.p$} ops/sec", m.iterations(), m.ops_per_sec());
+        if let Some(latency) = m.latency_summary(p) {
+            println!("stringify: {}", latency);
+        }
+        m.print_notes("stringify", p);
+        return;
+    }
+
+    // All three targets must produce the same bytes
+    let expected = serde_json::to_string(&parsed).unwrap();
+    let mut buf = Vec::with_capacity(expected.len());
+    serde_json::to_writer(&mut buf, &parsed).unwrap();
+    assert_eq!(serde_json::to_vec(&parsed).unwrap(), expected.as_bytes(), "to_vec output differs");
+    assert_eq!(buf, expected.as_bytes(), "to_writer output differs");
+
+    let to_string = bencher().run(|| {
+        let _ = serde_json::to_string(&parsed).unwrap();
+    });
+    let to_vec = bencher().run(|| {
+        let _ = serde_json::to_vec(&parsed).unwrap();
+    });
+    // clear() keeps the capacity, so after the first pass nothing allocates
+    let to_writer = bencher().run(|| {
+        buf.clear();
+        serde_json::to_writer(&mut buf, &parsed).unwrap();
+    });
+
+    for (name, m) in [("to_string", &to_string), ("to_vec", &to_vec), ("to_writer (reused)", &to_writer)] {
+        println!("{:<20} {} ops, {:.p$} ops/sec", format!("{}:", name), m.iterations(), m.ops_per_sec());
+        if let Some(latency) = m.latency_summary(p) {
+            println!("{:<20} {}", "", latency);
+        }
+        m.print_notes(name, p);
+    }
+    println!(
+        "Reused buffer vs to_string: {:.p$}x",
+        to_writer.ops_per_sec() / to_string.ops_per_sec()
+    );
+}
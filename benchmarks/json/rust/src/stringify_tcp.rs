@@ -0,0 +1,89 @@
+use clap::Parser;
+use harness::{Bencher, Options};
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::thread;
+
+#[derive(Parser)]
+#[command(about = "serde_json stringify-to-socket benchmark")]
+struct Args {
+    #[command(flatten)]
+    harness: Options,
+
+    /// Write straight to the TcpStream instead of through a BufWriter
+    #[arg(long)]
+    unbuffered: bool,
+}
+
+const ITERATIONS: usize = 20_000;
+
+// Accepts one connection on loopback and discards everything sent to it,
+// returning the byte count once the client shuts its side down
+fn sink() -> (u16, thread::JoinHandle<u64>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = thread::spawn(move || {
+        let (mut conn, _) = listener.accept().unwrap();
+        io::copy(&mut conn, &mut io::sink()).unwrap()
+    });
+    (port, handle)
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let data = fs::read_to_string("sample.json").unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&data).unwrap();
+    let len = serde_json::to_vec(&parsed).unwrap().len() as u64;
+    let p = opts.precision;
+
+    // The in-memory path this is contrasted with
+    let in_memory = Bencher::new(opts, ITERATIONS).warmup(100).run(|| {
+        let _ = serde_json::to_string(&parsed).unwrap();
+    });
+
+    let (port, server) = sink();
+    let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    stream.set_nodelay(true).unwrap();
+    let mut sent = 0u64;
+    // Each iteration is one complete response: serialized and flushed to the
+    // kernel before the next starts
+    let socket = if args.unbuffered {
+        let mut out = &stream;
+        Bencher::new(opts, ITERATIONS).warmup(100).run(|| {
+            serde_json::to_writer(&mut out, &parsed).unwrap();
+            sent += len;
+        })
+    } else {
+        let mut out = BufWriter::new(&stream);
+        Bencher::new(opts, ITERATIONS).warmup(100).run(|| {
+            serde_json::to_writer(&mut out, &parsed).unwrap();
+            out.flush().unwrap();
+            sent += len;
+        })
+    };
+    stream.shutdown(Shutdown::Write).unwrap();
+    let received = server.join().expect("Sink thread panicked");
+    assert_eq!(received, sent, "Sink received {} of {} bytes", received, sent);
+
+    let mb_per_sec = |ops_per_sec: f64| len as f64 * ops_per_sec / 1e6;
+    let target = if args.unbuffered { "TcpStream" } else { "BufWriter<TcpStream>" };
+    println!(
+        "stringify to memory: {} ops, {:.p$} MB/s",
+        in_memory.iterations(),
+        mb_per_sec(in_memory.ops_per_sec())
+    );
+    println!(
+        "stringify to socket: {} ops, {:.p$} MB/s ({})",
+        socket.iterations(),
+        mb_per_sec(socket.ops_per_sec()),
+        target
+    );
+    if let Some(latency) = socket.latency_summary(p) {
+        println!("stringify to socket: {}", latency);
+    }
+    println!("Socket vs memory: {:.p$}x", socket.ops_per_sec() / in_memory.ops_per_sec());
+    in_memory.print_notes("stringify to memory", p);
+    socket.print_notes("stringify to socket", p);
+}
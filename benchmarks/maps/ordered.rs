@@ -0,0 +1,120 @@
+// Ordered Map Benchmark: HashMap vs BTreeMap when iteration must be sorted
+// Times three phases for each map over the same N random u64 keys: building
+// the map, one in-order pass over every entry (HashMap has to collect and
+// sort its keys first) and N point lookups in a shuffled order
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::collections::{BTreeMap, HashMap};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 50;
+
+#[derive(Parser)]
+#[command(about = "HashMap vs BTreeMap for ordered iteration benchmark")]
+struct Args {
+    /// Entries per map
+    #[arg(long, default_value_t = 100_000, value_parser = in_range(1usize, 100_000_000))]
+    n: usize,
+
+    /// Seed for the keys and lookup order
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn build_hash(keys: &[u64]) -> HashMap<u64, u64> {
+    keys.iter().map(|&k| (k, k ^ 0xff)).collect()
+}
+
+fn build_btree(keys: &[u64]) -> BTreeMap<u64, u64> {
+    keys.iter().map(|&k| (k, k ^ 0xff)).collect()
+}
+
+// Order-sensitive fold over the values, so both maps must agree on order
+fn fold_ordered(values: impl Iterator<Item = u64>) -> u64 {
+    values.fold(0u64, |acc, v| acc.rotate_left(5) ^ v)
+}
+
+fn iter_hash(map: &HashMap<u64, u64>) -> u64 {
+    let mut keys: Vec<u64> = map.keys().copied().collect();
+    keys.sort_unstable();
+    fold_ordered(keys.iter().map(|k| map[k]))
+}
+
+fn iter_btree(map: &BTreeMap<u64, u64>) -> u64 {
+    fold_ordered(map.values().copied())
+}
+
+fn lookup_sum(lookups: &[u64], get: impl Fn(u64) -> Option<u64>) -> u64 {
+    lookups.iter().filter_map(|&k| get(k)).fold(0u64, u64::wrapping_add)
+}
+
+fn rate(n: usize, m: &Measurement) -> f64 {
+    n as f64 * m.ops_per_sec()
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+
+    let mut rng = XorShift64::new(args.seed);
+    let keys: Vec<u64> = (0..args.n).map(|_| rng.next_u64()).collect();
+    let mut lookups = keys.clone();
+    rng.shuffle(&mut lookups);
+
+    let hash = build_hash(&keys);
+    let btree = build_btree(&keys);
+    assert_eq!(iter_hash(&hash), iter_btree(&btree), "Maps iterate in different orders");
+    assert_eq!(
+        lookup_sum(&lookups, |k| hash.get(&k).copied()),
+        lookup_sum(&lookups, |k| btree.get(&k).copied()),
+        "Maps disagree on lookups"
+    );
+
+    let insert_hash = Bencher::new(opts, ITERATIONS).warmup(3).run(|| {
+        black_box(build_hash(black_box(&keys)));
+    });
+    let insert_btree = Bencher::new(opts, ITERATIONS).warmup(3).run(|| {
+        black_box(build_btree(black_box(&keys)));
+    });
+    let iter_hash_m = Bencher::new(opts, ITERATIONS).warmup(3).run(|| {
+        black_box(iter_hash(black_box(&hash)));
+    });
+    let iter_btree_m = Bencher::new(opts, ITERATIONS).warmup(3).run(|| {
+        black_box(iter_btree(black_box(&btree)));
+    });
+    let lookup_hash = Bencher::new(opts, ITERATIONS).warmup(3).run(|| {
+        black_box(lookup_sum(black_box(&lookups), |k| hash.get(&k).copied()));
+    });
+    let lookup_btree = Bencher::new(opts, ITERATIONS).warmup(3).run(|| {
+        black_box(lookup_sum(black_box(&lookups), |k| btree.get(&k).copied()));
+    });
+
+    let p = opts.precision;
+    let n = args.n;
+    println!("Benchmark: HashMap vs BTreeMap ({} u64 entries, seed {})", n, args.seed);
+    println!("Iterations: {}", ITERATIONS);
+    println!("{:<18} {:<18} {:<18} {:<10}", "Phase (ops/sec)", "HashMap", "BTreeMap", "BTreeMap vs HashMap");
+    let phases = [
+        ("Insert", &insert_hash, &insert_btree),
+        ("Ordered iteration", &iter_hash_m, &iter_btree_m),
+        ("Point lookup", &lookup_hash, &lookup_btree),
+    ];
+    for (phase, hash_m, btree_m) in phases {
+        let (hash_rate, btree_rate) = (rate(n, hash_m), rate(n, btree_m));
+        println!(
+            "{:<18} {:<18.0} {:<18.0} {:.p$}x",
+            phase,
+            hash_rate,
+            btree_rate,
+            btree_rate / hash_rate
+        );
+    }
+    for (phase, hash_m, btree_m) in phases {
+        hash_m.print_notes(&format!("{}, HashMap", phase), p);
+        btree_m.print_notes(&format!("{}, BTreeMap", phase), p);
+    }
+}
@@ -0,0 +1,235 @@
+// Huge Pages Benchmark: the same large buffer on 4KiB pages vs THP
+// Allocates a --mib buffer twice, once advised MADV_NOHUGEPAGE (forced
+// ordinary pages) and once MADV_HUGEPAGE (transparent huge pages), and runs
+// a random pointer chase (one TLB lookup per load, the worst case) and a
+// sequential sum over each. Large-buffer benchmarks that miss the TLB gain
+// from huge pages; this shows how much on the current machine.
+//
+// Linux only: it needs kernel THP support, with
+// /sys/kernel/mm/transparent_hugepage/enabled set to `madvise` or `always`.
+// Elsewhere, or with THP off, both buffers use ordinary pages and the
+// difference is noise; the output says so.
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 10;
+
+#[derive(Parser)]
+#[command(about = "Ordinary pages vs transparent huge pages benchmark")]
+struct Args {
+    /// Buffer size in MiB
+    #[arg(long, default_value_t = 256, value_parser = in_range(4usize, 65_536))]
+    mib: usize,
+
+    /// Loads per pointer-chase iteration
+    #[arg(long, default_value_t = 2_000_000, value_parser = in_range(1usize, 1_000_000_000))]
+    steps: usize,
+
+    /// Seed for the chase order
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+#[cfg(target_os = "linux")]
+mod pages {
+    use std::fs;
+    use std::io;
+    use std::ptr;
+    use std::slice;
+
+    /// An anonymous mapping of `len` u64s with a page-size hint.
+    pub struct Buffer {
+        ptr: *mut u64,
+        len: usize,
+    }
+
+    impl Buffer {
+        /// Maps the buffer and advises huge or ordinary pages. A refused hint
+        /// (no THP in this kernel) leaves the mapping usable and is returned
+        /// alongside it.
+        pub fn new(len: usize, huge: bool) -> io::Result<(Self, io::Result<()>)> {
+            let bytes = len * 8;
+            // SAFETY: a fresh private anonymous mapping; nothing else refers to it
+            let ptr = unsafe {
+                libc::mmap(
+                    ptr::null_mut(),
+                    bytes,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                    -1,
+                    0,
+                )
+            };
+            if ptr == libc::MAP_FAILED {
+                return Err(io::Error::last_os_error());
+            }
+            let advice = if huge { libc::MADV_HUGEPAGE } else { libc::MADV_NOHUGEPAGE };
+            // SAFETY: advises exactly the range just mapped
+            let advised = if unsafe { libc::madvise(ptr, bytes, advice) } == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::last_os_error())
+            };
+            Ok((Self { ptr: ptr.cast(), len }, advised))
+        }
+
+        pub fn as_mut_slice(&mut self) -> &mut [u64] {
+            // SAFETY: the mapping holds `len` zero-initialized u64s and lives as long as self
+            unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+        }
+    }
+
+    impl Drop for Buffer {
+        fn drop(&mut self) {
+            // SAFETY: unmaps the range mapped in new, once
+            unsafe {
+                libc::munmap(self.ptr.cast(), self.len * 8);
+            }
+        }
+    }
+
+    /// The selected mode in /sys/kernel/mm/transparent_hugepage/enabled
+    /// (`always`, `madvise` or `never`), or `None` without THP.
+    pub fn thp_mode() -> Option<String> {
+        let modes = fs::read_to_string("/sys/kernel/mm/transparent_hugepage/enabled").ok()?;
+        let start = modes.find('[')? + 1;
+        let end = start + modes[start..].find(']')?;
+        Some(modes[start..end].to_string())
+    }
+
+    /// KiB of this process's anonymous memory on huge pages.
+    pub fn anon_huge_kib() -> Option<u64> {
+        let rollup = fs::read_to_string("/proc/self/smaps_rollup").ok()?;
+        let line = rollup.lines().find(|line| line.starts_with("AnonHugePages:"))?;
+        line.split_whitespace().nth(1)?.parse().ok()
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod pages {
+    use std::io;
+
+    pub struct Buffer(Vec<u64>);
+
+    impl Buffer {
+        pub fn new(len: usize, _huge: bool) -> io::Result<(Self, io::Result<()>)> {
+            let refused = Err(io::Error::new(io::ErrorKind::Unsupported, "only supported on Linux"));
+            Ok((Self(vec![0; len]), refused))
+        }
+
+        pub fn as_mut_slice(&mut self) -> &mut [u64] {
+            &mut self.0
+        }
+    }
+
+    pub fn thp_mode() -> Option<String> {
+        None
+    }
+
+    pub fn anon_huge_kib() -> Option<u64> {
+        None
+    }
+}
+
+// Fills `buf` with a single random cycle (Sattolo's algorithm): buf[i] is
+// the index to load after i, so a chase visits every slot before repeating
+fn fill_cycle(buf: &mut [u64], seed: u64) {
+    for (i, slot) in buf.iter_mut().enumerate() {
+        *slot = i as u64;
+    }
+    let mut rng = XorShift64::new(seed);
+    for i in (1..buf.len()).rev() {
+        let j = rng.below(i as u64) as usize;
+        buf.swap(i, j);
+    }
+}
+
+fn chase(buf: &[u64], steps: usize) -> u64 {
+    let mut at = 0u64;
+    for _ in 0..steps {
+        at = buf[at as usize];
+    }
+    at
+}
+
+struct Run {
+    chase: Measurement,
+    scan: Measurement,
+    end: u64,
+    sum: u64,
+    huge_mib: Option<u64>,
+}
+
+fn measure(args: &Args, huge: bool) -> Run {
+    let len = args.mib * 1024 * 1024 / 8;
+    let before = pages::anon_huge_kib();
+    let (mut buffer, advised) = pages::Buffer::new(len, huge).expect("Failed to map buffer");
+    if let Err(e) = &advised {
+        eprintln!("madvise({}) not applied: {}", if huge { "MADV_HUGEPAGE" } else { "MADV_NOHUGEPAGE" }, e);
+    }
+    let buf = buffer.as_mut_slice();
+    // Filling faults every page in, which is when the kernel picks their size
+    fill_cycle(buf, args.seed);
+    let huge_mib = pages::anon_huge_kib().zip(before).map(|(after, before)| after.saturating_sub(before) / 1024);
+    let buf = &*buf;
+
+    let opts = &args.harness;
+    let chase_m = Bencher::new(opts, ITERATIONS).warmup(1).run(|| {
+        black_box(chase(black_box(buf), args.steps));
+    });
+    let scan_m = Bencher::new(opts, ITERATIONS).warmup(1).run(|| {
+        black_box(black_box(buf).iter().fold(0u64, |s, &x| s.wrapping_add(x)));
+    });
+    Run {
+        chase: chase_m,
+        scan: scan_m,
+        end: chase(buf, args.steps),
+        sum: buf.iter().fold(0u64, |s, &x| s.wrapping_add(x)),
+        huge_mib,
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    let p = args.harness.precision;
+    let mode = pages::thp_mode();
+
+    let small = measure(&args, false);
+    let huge = measure(&args, true);
+    assert_eq!((small.end, small.sum), (huge.end, huge.sum), "Buffers hold different data");
+
+    let loads = |m: &Measurement| args.steps as f64 * m.ops_per_sec();
+    let gib_per_sec = |m: &Measurement| args.mib as f64 / 1024.0 * m.ops_per_sec();
+    let backed = |run: &Run| run.huge_mib.map_or("unknown".to_string(), |mib| format!("{} MiB", mib));
+
+    println!("Benchmark: 4KiB pages vs transparent huge pages ({} MiB buffer)", args.mib);
+    println!("THP mode: {}", mode.as_deref().unwrap_or("unavailable"));
+    println!("Iterations: {}", ITERATIONS);
+    println!("{:<16} {:<20} {:<16} {:<16}", "Pages", "Random chase", "Sequential sum", "On huge pages");
+    for (name, run) in [("4KiB (NOHUGE)", &small), ("THP (HUGEPAGE)", &huge)] {
+        println!(
+            "{:<16} {:<20} {:<16} {}",
+            name,
+            format!("{:.0} loads/s", loads(&run.chase)),
+            format!("{:.p$} GiB/s", gib_per_sec(&run.scan)),
+            backed(run)
+        );
+    }
+    for (name, run) in [("4KiB", &small), ("THP", &huge)] {
+        run.chase.print_notes(&format!("{} random chase", name), p);
+        run.scan.print_notes(&format!("{} sequential sum", name), p);
+    }
+    println!(
+        "Huge page speedup: random {:.p$}x, sequential {:.p$}x",
+        loads(&huge.chase) / loads(&small.chase),
+        gib_per_sec(&huge.scan) / gib_per_sec(&small.scan)
+    );
+    if huge.huge_mib == Some(0) || !matches!(mode.as_deref(), Some("always" | "madvise")) {
+        println!("Note: no huge pages were used, so the two rows measure the same thing");
+    }
+}
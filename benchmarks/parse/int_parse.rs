@@ -0,0 +1,123 @@
+// Integer Parsing Benchmark: str::parse::<i64>() vs a hand-rolled byte parser
+// Parses a generated list of decimal integers; with --malformed a share of
+// them are corrupted so the error path is timed too
+use clap::Parser;
+use harness::{in_range, Bencher, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 500;
+
+#[derive(Parser)]
+#[command(about = "Integer parsing benchmark")]
+struct Args {
+    /// Integers in the generated list
+    #[arg(long, default_value_t = 100_000, value_parser = in_range(1usize, 100_000_000))]
+    count: usize,
+
+    /// Corrupt one input in every --malformed-every to time the error path
+    #[arg(long)]
+    malformed: bool,
+
+    /// Spacing of malformed inputs
+    #[arg(long, value_name = "N", default_value_t = 10, requires = "malformed", value_parser = in_range(1usize, 1_000_000))]
+    malformed_every: usize,
+
+    /// Seed for the generated integers
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// Seeded integers of every width, both signs (xorshift64); every
+// `malformed_every`-th one gets a stray character, an overflow or is empty
+fn generate(count: usize, seed: u64, malformed_every: Option<usize>) -> Vec<String> {
+    let mut state = seed.max(1);
+    (0..count)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let digits = state % 19 + 1;
+            let value = (state >> 8) as i64 % 10i64.pow(digits as u32 - 1).max(10);
+            let text = if state & 1 == 0 { value.to_string() } else { (-value).to_string() };
+            match malformed_every {
+                Some(n) if i % n == n - 1 => match state % 3 {
+                    0 => format!("{}x", text),
+                    1 => "99999999999999999999".to_string(),
+                    _ => String::new(),
+                },
+                _ => text,
+            }
+        })
+        .collect()
+}
+
+// Optional sign, then ASCII digits accumulated with overflow checks; no
+// leading '+', whitespace or underscores
+fn parse_bytes(s: &str) -> Option<i64> {
+    let (negative, digits) = match s.as_bytes() {
+        [b'-', rest @ ..] => (true, rest),
+        rest => (false, rest),
+    };
+    if digits.is_empty() {
+        return None;
+    }
+    let mut value: i64 = 0;
+    for &b in digits {
+        let digit = b.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        // Accumulate negatively so i64::MIN parses without overflow
+        value = value.checked_mul(10)?.checked_sub(digit as i64)?;
+    }
+    if negative {
+        Some(value)
+    } else {
+        value.checked_neg()
+    }
+}
+
+// Sum of the parsed values and the number of inputs that failed
+fn parse_all(inputs: &[String], parse: impl Fn(&str) -> Option<i64>) -> (i64, usize) {
+    inputs.iter().fold((0i64, 0usize), |(sum, errors), s| match parse(s) {
+        Some(v) => (sum.wrapping_add(v), errors),
+        None => (sum, errors + 1),
+    })
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let inputs = generate(args.count, args.seed, args.malformed.then_some(args.malformed_every));
+
+    let std_parse = |s: &str| s.parse::<i64>().ok();
+    for s in &inputs {
+        assert_eq!(std_parse(s), parse_bytes(s), "Parsers disagree on {:?}", s);
+    }
+    let (_, errors) = parse_all(&inputs, std_parse);
+
+    let std_m = Bencher::new(opts, ITERATIONS).warmup(10).run(|| {
+        black_box(parse_all(black_box(&inputs), std_parse));
+    });
+    let bytes_m = Bencher::new(opts, ITERATIONS).warmup(10).run(|| {
+        black_box(parse_all(black_box(&inputs), parse_bytes));
+    });
+
+    let p = opts.precision;
+    let std_rate = args.count as f64 * std_m.ops_per_sec();
+    let bytes_rate = args.count as f64 * bytes_m.ops_per_sec();
+
+    println!("Benchmark: Integer parsing ({} inputs, seed {})", args.count, args.seed);
+    if args.malformed {
+        println!("Malformed: {} (one per {} inputs)", errors, args.malformed_every);
+    }
+    println!("Iterations: {}", ITERATIONS);
+    println!("str::parse::<i64>(): {:.0} parses/sec", std_rate);
+    println!("Byte parser:         {:.0} parses/sec", bytes_rate);
+    println!("Byte parser speedup: {:.p$}x", bytes_rate / std_rate);
+    std_m.print_notes("str::parse::<i64>()", p);
+    bytes_m.print_notes("Byte parser", p);
+}
@@ -0,0 +1,92 @@
+// Percentile Benchmark: p50/p95/p99 via full sort vs selection
+// Computes three nearest-rank percentiles of N latency-like u64 values, once
+// by sorting the whole copy and indexing, and once with select_nth_unstable
+// (quickselect), each selection narrowed to the part above the previous
+// percentile. Selection is O(n) per percentile instead of O(n log n) for the
+// sort, which wins while only a few percentiles are needed. Each iteration
+// gets a fresh unsorted copy, built outside the timed region.
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 50;
+const PERCENTILES: [f64; 3] = [50.0, 95.0, 99.0];
+
+#[derive(Parser)]
+#[command(about = "Percentiles by sorting vs selection benchmark")]
+struct Args {
+    /// Values in the dataset
+    #[arg(long, default_value_t = 1_000_000, value_parser = in_range(1usize, 1_000_000_000))]
+    n: usize,
+
+    /// Seed for the values
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// Nearest rank: the smallest value with at least p% of the data at or below it
+fn rank(p: f64, n: usize) -> usize {
+    ((p / 100.0 * n as f64).ceil() as usize).clamp(1, n) - 1
+}
+
+fn by_sort(mut values: Vec<u64>) -> [u64; 3] {
+    values.sort_unstable();
+    PERCENTILES.map(|p| values[rank(p, values.len())])
+}
+
+// PERCENTILES is ascending, so each selection only has to search above the last
+fn by_select(mut values: Vec<u64>) -> [u64; 3] {
+    let n = values.len();
+    let mut from = 0;
+    PERCENTILES.map(|p| {
+        let i = rank(p, n);
+        let (_, &mut v, _) = values[from..].select_nth_unstable(i - from);
+        from = i;
+        v
+    })
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    // Long-tailed, like request latencies: mostly small, a few very large
+    let mut rng = XorShift64::new(args.seed);
+    let values: Vec<u64> = (0..args.n)
+        .map(|_| {
+            let u = rng.next_f64().max(f64::MIN_POSITIVE);
+            (1_000.0 / u.sqrt()) as u64
+        })
+        .collect();
+
+    let expected = by_sort(values.clone());
+    assert_eq!(by_select(values.clone()), expected, "Sort and selection percentiles differ");
+
+    let sort = Bencher::new(opts, ITERATIONS).warmup(2).run_with_setup(
+        || values.clone(),
+        |v| {
+            black_box(by_sort(v));
+        },
+    );
+    let select = Bencher::new(opts, ITERATIONS).warmup(2).run_with_setup(
+        || values.clone(),
+        |v| {
+            black_box(by_select(v));
+        },
+    );
+
+    let p = opts.precision;
+    let ms = |m: &Measurement| m.per_iter().as_secs_f64() * 1000.0;
+
+    println!("Benchmark: Percentiles of {} values (seed {})", args.n, args.seed);
+    println!("Iterations: {}", ITERATIONS);
+    println!("p50 {}, p95 {}, p99 {}", expected[0], expected[1], expected[2]);
+    println!("Full sort:           {:.p$} ms", ms(&sort));
+    println!("select_nth_unstable: {:.p$} ms", ms(&select));
+    println!("Selection speedup:   {:.p$}x", ms(&sort) / ms(&select));
+    sort.print_notes("Full sort", p);
+    select.print_notes("select_nth_unstable", p);
+}
@@ -0,0 +1,144 @@
+// Concurrent Queue Benchmark: crossbeam ArrayQueue, SPSC vs MPMC
+// Pushes the same number of messages through a bounded lock-free queue with
+// one producer and one consumer, then with --producers and --consumers
+// threads, to show what contention on both ends costs. A full queue makes
+// producers yield, an empty one makes consumers yield.
+use clap::Parser;
+use crossbeam_queue::ArrayQueue;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+const ITERATIONS: usize = 10;
+
+#[derive(Parser)]
+#[command(about = "SPSC vs MPMC lock-free queue throughput benchmark")]
+struct Args {
+    /// Messages per run, split evenly between producers
+    #[arg(long, default_value_t = 1_000_000, value_parser = in_range(1usize, 1_000_000_000))]
+    messages: usize,
+
+    /// Producer threads in the MPMC run
+    #[arg(long, default_value_t = 4, value_parser = in_range(1usize, 256))]
+    producers: usize,
+
+    /// Consumer threads in the MPMC run
+    #[arg(long, default_value_t = 4, value_parser = in_range(1usize, 256))]
+    consumers: usize,
+
+    /// Queue capacity
+    #[arg(long, default_value_t = 1024, value_parser = in_range(1usize, 1 << 24))]
+    capacity: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+#[derive(Clone, Copy)]
+struct Shape {
+    producers: usize,
+    consumers: usize,
+}
+
+// Producer p sends p * per_producer .. (p + 1) * per_producer, so every
+// message is distinct. Returns how many were received and, with `record`,
+// the messages themselves.
+fn exchange(shape: Shape, capacity: usize, per_producer: usize, record: bool) -> (usize, Vec<u64>) {
+    let queue = ArrayQueue::new(capacity);
+    let finished = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for p in 0..shape.producers {
+            let (queue, finished) = (&queue, &finished);
+            s.spawn(move || {
+                let base = (p * per_producer) as u64;
+                for i in 0..per_producer as u64 {
+                    let mut message = base + i;
+                    while let Err(back) = queue.push(message) {
+                        message = back;
+                        thread::yield_now();
+                    }
+                }
+                finished.fetch_add(1, Ordering::Release);
+            });
+        }
+        let consumers: Vec<_> = (0..shape.consumers)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut count = 0;
+                    let mut seen = Vec::new();
+                    let mut take = |message: u64| {
+                        count += 1;
+                        if record {
+                            seen.push(message);
+                        }
+                    };
+                    loop {
+                        match queue.pop() {
+                            Some(message) => take(message),
+                            // Every producer is done, so what is left is final
+                            None if finished.load(Ordering::Acquire) == shape.producers => {
+                                while let Some(message) = queue.pop() {
+                                    take(message);
+                                }
+                                break;
+                            }
+                            None => thread::yield_now(),
+                        }
+                    }
+                    (count, seen)
+                })
+            })
+            .collect();
+        consumers
+            .into_iter()
+            .map(|c| c.join().expect("Consumer panicked"))
+            .fold((0, Vec::new()), |(total, mut all), (count, seen)| {
+                all.extend(seen);
+                (total + count, all)
+            })
+    })
+}
+
+fn measure(opts: &Options, shape: Shape, capacity: usize, messages: usize) -> (usize, Measurement) {
+    let per_producer = messages / shape.producers;
+    let total = per_producer * shape.producers;
+
+    // Every message arrives exactly once: nothing lost, nothing duplicated
+    let (count, mut seen) = exchange(shape, capacity, per_producer, true);
+    seen.sort_unstable();
+    assert_eq!(count, total, "Received {} of {} messages", count, total);
+    assert!(seen.iter().copied().eq(0..total as u64), "Messages lost or duplicated");
+
+    let m = Bencher::new(opts, ITERATIONS).warmup(1).run(|| {
+        let (count, _) = exchange(shape, capacity, black_box(per_producer), false);
+        assert_eq!(count, total, "Messages lost");
+    });
+    (total, m)
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+
+    let spsc = Shape { producers: 1, consumers: 1 };
+    let mpmc = Shape {
+        producers: args.producers,
+        consumers: args.consumers,
+    };
+    let (spsc_total, spsc_m) = measure(opts, spsc, args.capacity, args.messages);
+    let (mpmc_total, mpmc_m) = measure(opts, mpmc, args.capacity, args.messages);
+
+    let p = opts.precision;
+    let spsc_rate = spsc_total as f64 * spsc_m.ops_per_sec();
+    let mpmc_rate = mpmc_total as f64 * mpmc_m.ops_per_sec();
+
+    println!("Benchmark: ArrayQueue throughput (capacity {})", args.capacity);
+    println!("Messages/run: {}", args.messages);
+    println!("Iterations: {}", ITERATIONS);
+    println!("SPSC (1P/1C):   {:.0} messages/sec", spsc_rate);
+    println!("MPMC ({}P/{}C): {:.0} messages/sec", args.producers, args.consumers, mpmc_rate);
+    println!("MPMC vs SPSC:   {:.p$}x", mpmc_rate / spsc_rate);
+    spsc_m.print_notes("SPSC", p);
+    mpmc_m.print_notes("MPMC", p);
+}
@@ -0,0 +1,55 @@
+// Refcount Benchmark: Rc::clone vs Arc::clone, single-threaded
+// Each iteration clones and drops one shared pointer CLONES times. Rc bumps a
+// plain counter; Arc uses an atomic increment and decrement even when only
+// one thread ever touches it, which is the overhead measured here.
+use clap::Parser;
+use harness::{in_range, Bencher, Options};
+use std::hint::black_box;
+use std::rc::Rc;
+use std::sync::Arc;
+
+const ITERATIONS: usize = 1000;
+
+#[derive(Parser)]
+#[command(about = "Rc vs Arc clone cost benchmark")]
+struct Args {
+    /// Clone/drop pairs per iteration
+    #[arg(long, default_value_t = 100_000, value_parser = in_range(1usize, 100_000_000))]
+    clones: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let rc = Rc::new(vec![0u64; 16]);
+    let arc = Arc::new(vec![0u64; 16]);
+
+    let rc_m = Bencher::new(opts, ITERATIONS).warmup(20).run(|| {
+        for _ in 0..args.clones {
+            black_box(Rc::clone(black_box(&rc)));
+        }
+    });
+    let arc_m = Bencher::new(opts, ITERATIONS).warmup(20).run(|| {
+        for _ in 0..args.clones {
+            black_box(Arc::clone(black_box(&arc)));
+        }
+    });
+    // Every clone was dropped again
+    assert_eq!(Rc::strong_count(&rc), 1, "Rc clones leaked");
+    assert_eq!(Arc::strong_count(&arc), 1, "Arc clones leaked");
+
+    let p = opts.precision;
+    let rc_rate = args.clones as f64 * rc_m.ops_per_sec();
+    let arc_rate = args.clones as f64 * arc_m.ops_per_sec();
+
+    println!("Benchmark: Rc vs Arc clone+drop ({} per iteration)", args.clones);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Rc::clone:  {:.0} clones/sec", rc_rate);
+    println!("Arc::clone: {:.0} clones/sec", arc_rate);
+    println!("Arc overhead: {:.p$}x", rc_rate / arc_rate);
+    rc_m.print_notes("Rc::clone", p);
+    arc_m.print_notes("Arc::clone", p);
+}
@@ -0,0 +1,103 @@
+// Search Benchmark: binary_search vs linear scan over a sorted Vec<u32>
+// Sweeps the array size in powers of two to find the crossover where the
+// O(log n) binary search overtakes the branch-predictable, cache-friendly
+// O(n) scan. Half of the random keys are present, half fall between elements.
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 200;
+const MIN_SIZE: usize = 1;
+
+#[derive(Parser)]
+#[command(about = "Binary search vs linear scan crossover")]
+struct Args {
+    /// Largest array size in the sweep (sizes double from 1 up to this)
+    #[arg(long, default_value_t = 4096, value_parser = in_range(MIN_SIZE, 1 << 24))]
+    max_size: usize,
+
+    /// Random keys looked up per iteration
+    #[arg(long, default_value_t = 256, value_parser = in_range(1usize, 1_000_000))]
+    queries: usize,
+
+    /// Seed for the keys
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// Stops at the first element not below the key, as a sorted scan can
+fn linear_search(v: &[u32], key: u32) -> Option<usize> {
+    let i = v.iter().take_while(|&&x| x < key).count();
+    (v.get(i) == Some(&key)).then_some(i)
+}
+
+fn binary_search(v: &[u32], key: u32) -> Option<usize> {
+    v.binary_search(&key).ok()
+}
+
+fn time_search(opts: &Options, v: &[u32], keys: &[u32], search: fn(&[u32], u32) -> Option<usize>) -> Measurement {
+    Bencher::new(opts, ITERATIONS).warmup(5).run(|| {
+        for &key in keys {
+            black_box(search(black_box(v), key));
+        }
+    })
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let mut rng = XorShift64::new(args.seed);
+
+    let p = opts.precision;
+    println!(
+        "Benchmark: binary_search vs linear scan (sorted Vec<u32>, {} keys per iteration, seed {})",
+        args.queries, args.seed
+    );
+    println!("Iterations: {}", ITERATIONS);
+    println!("{:<10} {:<20} {:<20} {:<10}", "Size", "Linear (search/s)", "Binary (search/s)", "Binary vs linear");
+
+    // Sizes at which binary search was faster, for the crossover line
+    let mut binary_wins = Vec::new();
+    let mut size = MIN_SIZE;
+    while size <= args.max_size {
+        // Even values only, so odd keys are misses that land between elements
+        let v: Vec<u32> = (0..size as u32).map(|i| i * 2).collect();
+        let keys: Vec<u32> = (0..args.queries).map(|_| rng.below(2 * size as u64) as u32).collect();
+        for &key in &keys {
+            assert_eq!(linear_search(&v, key), binary_search(&v, key), "Searches disagree on key {}", key);
+        }
+
+        let linear = time_search(opts, &v, &keys, linear_search);
+        let binary = time_search(opts, &v, &keys, binary_search);
+        let linear_rate = args.queries as f64 * linear.ops_per_sec();
+        let binary_rate = args.queries as f64 * binary.ops_per_sec();
+        println!(
+            "{:<10} {:<20.0} {:<20.0} {:<10}",
+            size,
+            linear_rate,
+            binary_rate,
+            format!("{:.p$}x", binary_rate / linear_rate)
+        );
+        binary_wins.push((size, binary_rate > linear_rate));
+        linear.print_notes(&format!("{} linear", size), p);
+        binary.print_notes(&format!("{} binary", size), p);
+        size *= 2;
+    }
+
+    // The smallest size from which binary search wins at every larger size
+    let crossover = binary_wins
+        .iter()
+        .rev()
+        .take_while(|(_, wins)| *wins)
+        .last()
+        .map(|(size, _)| *size);
+    match crossover {
+        Some(MIN_SIZE) => println!("Crossover: none, binary search wins at every size"),
+        Some(size) => println!("Crossover: binary search wins from {} elements", size),
+        None => println!("Crossover: linear scan still wins at {} elements", binary_wins.last().map_or(0, |w| w.0)),
+    }
+}
@@ -0,0 +1,85 @@
+// Dot Product Benchmark: plain loop vs explicit 8-lane chunks
+// The plain loop is one serial chain of f32 adds, which the compiler may not
+// reorder into vector lanes; --explicit-simd adds a version with eight
+// independent accumulators over chunks_exact(8), which it can. std::simd
+// would express the same thing but is nightly-only.
+use clap::Parser;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::hint::black_box;
+
+const LANES: usize = 8;
+const ITERATIONS: usize = 500;
+
+#[derive(Parser)]
+#[command(about = "f32 dot product benchmark")]
+struct Args {
+    /// Elements per vector
+    #[arg(long, default_value_t = 1 << 20, value_parser = in_range(LANES, 1 << 28))]
+    len: usize,
+
+    /// Also time the explicit 8-lane chunked version
+    #[arg(long)]
+    explicit_simd: bool,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn dot_plain(a: &[f32], b: &[f32]) -> f32 {
+    let mut sum = 0.0f32;
+    for i in 0..a.len() {
+        sum += a[i] * b[i];
+    }
+    sum
+}
+
+// Lane-wise partial sums, combined once at the end; the tail that doesn't
+// fill a chunk is added serially
+fn dot_chunked(a: &[f32], b: &[f32]) -> f32 {
+    let mut lanes = [0.0f32; LANES];
+    let (a_chunks, b_chunks) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+    let tail = dot_plain(a_chunks.remainder(), b_chunks.remainder());
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for lane in 0..LANES {
+            lanes[lane] += x[lane] * y[lane];
+        }
+    }
+    lanes.iter().sum::<f32>() + tail
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    // Small values keep the f32 sum well inside its precision
+    let a: Vec<f32> = (0..args.len).map(|i| ((i % 97) as f32 - 48.0) / 64.0).collect();
+    let b: Vec<f32> = (0..args.len).map(|i| ((i % 89) as f32 - 44.0) / 64.0).collect();
+
+    let plain = Bencher::new(opts, ITERATIONS).warmup(10).run(|| {
+        black_box(dot_plain(black_box(&a), black_box(&b)));
+    });
+
+    let p = opts.precision;
+    // One multiply and one add per element
+    let gflops = |m: &Measurement| 2.0 * args.len as f64 * m.ops_per_sec() / 1e9;
+    println!("Benchmark: f32 dot product ({} elements)", args.len);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Plain loop:       {:.p$} GFLOP/s", gflops(&plain));
+    plain.print_notes("Plain loop", p);
+
+    if args.explicit_simd {
+        // Summation order differs, so compare with a tolerance
+        let (expected, chunked_sum) = (dot_plain(&a, &b) as f64, dot_chunked(&a, &b) as f64);
+        assert!(
+            (expected - chunked_sum).abs() <= 1e-3 * expected.abs().max(1.0),
+            "Chunked dot {} != plain {}",
+            chunked_sum,
+            expected
+        );
+        let chunked = Bencher::new(opts, ITERATIONS).warmup(10).run(|| {
+            black_box(dot_chunked(black_box(&a), black_box(&b)));
+        });
+        println!("8-lane chunks:    {:.p$} GFLOP/s", gflops(&chunked));
+        println!("Explicit speedup: {:.p$}x", gflops(&chunked) / gflops(&plain));
+        chunked.print_notes("8-lane chunks", p);
+    }
+}
@@ -0,0 +1,187 @@
+// Concurrent Stack Benchmark: Mutex<Vec<T>> vs crossbeam SegQueue
+// --threads threads share one structure and each runs the same random mix
+// of pushes and pops (--push-ratio of them pushes), starting from --prefill
+// elements. The Vec behind a Mutex is a true LIFO stack where every
+// operation takes the lock. crossbeam has no lock-free stack, and its
+// lock-free SegQueue pops in FIFO order, so the comparison is between the
+// locking and lock-free ways to share a push/pop pool when element order
+// does not matter. A pop that finds the structure empty counts as an
+// operation.
+use clap::Parser;
+use crossbeam_queue::SegQueue;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::hint::black_box;
+use std::sync::Mutex;
+use std::thread;
+
+const ITERATIONS: usize = 10;
+
+#[derive(Parser)]
+#[command(about = "Mutex-protected vs lock-free concurrent stack benchmark")]
+struct Args {
+    /// Threads pushing and popping
+    #[arg(long, default_value_t = 4, value_parser = in_range(1usize, 256))]
+    threads: usize,
+
+    /// Operations per run, split evenly between threads
+    #[arg(long, default_value_t = 1_000_000, value_parser = in_range(1usize, 1_000_000_000))]
+    ops: usize,
+
+    /// Fraction of operations that are pushes; the rest are pops
+    #[arg(long, default_value_t = 0.5, value_parser = in_range(0.0, 1.0))]
+    push_ratio: f64,
+
+    /// Elements in the structure before the threads start
+    #[arg(long, default_value_t = 1024, value_parser = in_range(0usize, 1 << 24))]
+    prefill: usize,
+
+    /// Seed for the push/pop mix
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+trait Stack: Default + Sync {
+    fn push(&self, value: u64);
+    fn pop(&self) -> Option<u64>;
+}
+
+impl Stack for Mutex<Vec<u64>> {
+    fn push(&self, value: u64) {
+        self.lock().unwrap().push(value);
+    }
+
+    fn pop(&self) -> Option<u64> {
+        self.lock().unwrap().pop()
+    }
+}
+
+impl Stack for SegQueue<u64> {
+    fn push(&self, value: u64) {
+        SegQueue::push(self, value);
+    }
+
+    fn pop(&self) -> Option<u64> {
+        SegQueue::pop(self)
+    }
+}
+
+// Every value is distinct: the owner (a thread index, or `threads` for the
+// prefill) in the high half, a counter in the low half
+fn tag(owner: usize, i: usize) -> u64 {
+    ((owner as u64) << 32) | i as u64
+}
+
+#[derive(Default)]
+struct Outcome {
+    pushes: usize,
+    pops: usize,
+    pushed: Vec<u64>,
+    popped: Vec<u64>,
+}
+
+// Runs every thread's plan (true = push) against a fresh structure and
+// drains what remains into `popped`. Counts every successful push and pop;
+// with `record`, keeps the values too.
+fn exchange<S: Stack>(plans: &[Vec<bool>], prefill: usize, record: bool) -> Outcome {
+    let stack = S::default();
+    for i in 0..prefill {
+        stack.push(tag(plans.len(), i));
+    }
+    let mut outcome = thread::scope(|s| {
+        let workers: Vec<_> = plans
+            .iter()
+            .enumerate()
+            .map(|(t, plan)| {
+                let stack = &stack;
+                s.spawn(move || {
+                    let mut outcome = Outcome::default();
+                    for (i, &push) in plan.iter().enumerate() {
+                        if push {
+                            let value = tag(t, i);
+                            stack.push(value);
+                            outcome.pushes += 1;
+                            if record {
+                                outcome.pushed.push(value);
+                            }
+                        } else if let Some(value) = stack.pop() {
+                            outcome.pops += 1;
+                            if record {
+                                outcome.popped.push(value);
+                            }
+                        }
+                    }
+                    outcome
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|w| w.join().expect("Worker panicked"))
+            .fold(Outcome::default(), |mut all, o| {
+                all.pushes += o.pushes;
+                all.pops += o.pops;
+                all.pushed.extend(o.pushed);
+                all.popped.extend(o.popped);
+                all
+            })
+    });
+    while let Some(value) = stack.pop() {
+        outcome.pops += 1;
+        if record {
+            outcome.popped.push(value);
+        }
+    }
+    outcome
+}
+
+fn measure<S: Stack>(opts: &Options, plans: &[Vec<bool>], prefill: usize) -> Measurement {
+    // Nothing lost, nothing duplicated: everything popped (during the run or
+    // drained after it) is exactly the prefill plus everything pushed
+    let outcome = exchange::<S>(plans, prefill, true);
+    let mut put: Vec<u64> = (0..prefill).map(|i| tag(plans.len(), i)).chain(outcome.pushed).collect();
+    let mut taken = outcome.popped;
+    put.sort_unstable();
+    taken.sort_unstable();
+    assert_eq!(put.len(), taken.len(), "Put {} elements, got {} back", put.len(), taken.len());
+    assert!(put == taken, "Elements lost or duplicated");
+
+    Bencher::new(opts, ITERATIONS).warmup(1).run(|| {
+        let outcome = exchange::<S>(black_box(plans), prefill, false);
+        assert_eq!(outcome.pops, prefill + outcome.pushes, "Elements lost");
+    })
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+
+    let per_thread = args.ops / args.threads;
+    let total = per_thread * args.threads;
+    let mut rng = XorShift64::new(args.seed);
+    let plans: Vec<Vec<bool>> = (0..args.threads)
+        .map(|_| (0..per_thread).map(|_| rng.next_f64() < args.push_ratio).collect())
+        .collect();
+
+    let mutex_m = measure::<Mutex<Vec<u64>>>(opts, &plans, args.prefill);
+    let segqueue_m = measure::<SegQueue<u64>>(opts, &plans, args.prefill);
+
+    let p = opts.precision;
+    let rate = |m: &Measurement| total as f64 * m.ops_per_sec();
+    println!(
+        "Benchmark: Concurrent stack, {} threads, {:.0}% pushes, {} prefilled",
+        args.threads,
+        args.push_ratio * 100.0,
+        args.prefill
+    );
+    println!("Operations/run: {}", total);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Mutex<Vec>:         {:.0} ops/sec", rate(&mutex_m));
+    println!("SegQueue:           {:.0} ops/sec", rate(&segqueue_m));
+    println!("SegQueue vs Mutex:  {:.p$}x", rate(&segqueue_m) / rate(&mutex_m));
+    mutex_m.print_notes("Mutex<Vec>", p);
+    segqueue_m.print_notes("SegQueue", p);
+}
@@ -0,0 +1,143 @@
+// Summary Statistics Benchmark: single-pass mean/variance/min/max (Welford)
+// over a large f64 array, sequential or merged from rayon partials
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Options};
+use rayon::prelude::*;
+use std::hint::black_box;
+
+const ITERATIONS: usize = 20;
+const CHUNK: usize = 64 * 1024; // elements per parallel partial
+
+#[derive(Parser)]
+#[command(about = "Single-pass summary statistics benchmark")]
+struct Args {
+    /// Number of f64 elements
+    #[arg(long, default_value_t = 10_000_000, value_parser = in_range(1usize, 1_000_000_000))]
+    n: usize,
+
+    /// Compute per-chunk accumulators on rayon and merge them
+    #[arg(long)]
+    parallel: bool,
+
+    /// Seed for the generated data
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Welford {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Welford {
+    fn new() -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    // Chan et al. pairwise combination of two partial accumulators
+    fn merge(self, other: Self) -> Self {
+        if self.count == 0 {
+            return other;
+        }
+        if other.count == 0 {
+            return self;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        Self {
+            count,
+            mean: self.mean + delta * other.count as f64 / count as f64,
+            m2: self.m2 + other.m2 + delta * delta * self.count as f64 * other.count as f64 / count as f64,
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        self.m2 / self.count as f64
+    }
+}
+
+fn sequential(data: &[f64]) -> Welford {
+    let mut acc = Welford::new();
+    for &x in data {
+        acc.push(x);
+    }
+    acc
+}
+
+fn parallel(data: &[f64]) -> Welford {
+    data.par_chunks(CHUNK)
+        .map(sequential)
+        .reduce(Welford::new, Welford::merge)
+}
+
+fn assert_close(name: &str, got: f64, want: f64) {
+    assert!(
+        (got - want).abs() <= 1e-9 * want.abs().max(1.0),
+        "{} {} != two-pass {}",
+        name,
+        got,
+        want
+    );
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+
+    // Values around 1e6 with unit spread, where a naive sum-of-squares
+    // variance loses most of its precision
+    let mut rng = XorShift64::new(args.seed);
+    let data: Vec<f64> = (0..args.n).map(|_| 1e6 + rng.next_f64()).collect();
+
+    // Check the single-pass result against the exact two-pass formulas
+    let stats = if args.parallel { parallel(&data) } else { sequential(&data) };
+    let mean = data.iter().sum::<f64>() / data.len() as f64;
+    let variance = data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / data.len() as f64;
+    assert_close("mean", stats.mean, mean);
+    assert_close("variance", stats.variance(), variance);
+
+    let m = Bencher::new(opts, ITERATIONS).warmup(2).run(|| {
+        let data = black_box(&data[..]);
+        black_box(if args.parallel { parallel(data) } else { sequential(data) });
+    });
+
+    let p = opts.precision;
+    let mode = if args.parallel { "rayon, merged partials" } else { "sequential" };
+    println!("Benchmark: Summary statistics ({})", mode);
+    println!("Elements: {}", args.n);
+    println!(
+        "Mean: {:.6}  Stddev: {:.6}  Min: {:.6}  Max: {:.6}",
+        stats.mean,
+        stats.variance().sqrt(),
+        stats.min,
+        stats.max
+    );
+    println!("Time: {:.p$}ms/pass", m.per_iter().as_secs_f64() * 1000.0);
+    println!("Elements/sec: {:.0}", args.n as f64 * m.ops_per_sec());
+    m.print_notes("Time", p);
+}
@@ -0,0 +1,104 @@
+// String Concatenation Benchmark: building one large string from N fragments
+// Compares the usual idioms over the same pre-built fragments: `s = s + f`
+// (which reuses s's buffer, like push_str), `s = format!("{}{}", s, f)`
+// (which copies the whole string every time, the quadratic footgun),
+// push_str into a String::with_capacity, itertools::join and
+// collect::<String>(). All five must build the same string.
+use clap::Parser;
+use harness::{in_range, Bencher, Measurement, Options};
+use itertools::Itertools;
+use std::hint::black_box;
+
+const ITERATIONS: usize = 20;
+
+type Build = fn(&[String]) -> String;
+
+#[derive(Parser)]
+#[command(about = "String concatenation strategies benchmark")]
+struct Args {
+    /// Fragments per string
+    #[arg(long, default_value_t = 10_000, value_parser = in_range(1usize, 10_000_000))]
+    fragments: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn plus(fragments: &[String]) -> String {
+    let mut s = String::new();
+    for f in fragments {
+        s = s + f;
+    }
+    s
+}
+
+fn format_loop(fragments: &[String]) -> String {
+    let mut s = String::new();
+    for f in fragments {
+        s = format!("{}{}", s, f);
+    }
+    s
+}
+
+fn with_capacity(fragments: &[String]) -> String {
+    let mut s = String::with_capacity(fragments.iter().map(String::len).sum());
+    for f in fragments {
+        s.push_str(f);
+    }
+    s
+}
+
+fn join(fragments: &[String]) -> String {
+    fragments.iter().join("")
+}
+
+fn collect(fragments: &[String]) -> String {
+    fragments.iter().map(String::as_str).collect()
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let fragments: Vec<String> = (0..args.fragments).map(|i| format!("item-{},", i)).collect();
+
+    let strategies: [(&str, Build); 5] = [
+        ("+ operator", plus),
+        ("format! in a loop", format_loop),
+        ("with_capacity + push_str", with_capacity),
+        ("itertools::join", join),
+        ("collect::<String>()", collect),
+    ];
+
+    let expected = with_capacity(&fragments);
+    for (name, build) in &strategies {
+        assert_eq!(build(&fragments), expected, "{} built a different string", name);
+    }
+
+    let results: Vec<(&str, Measurement)> = strategies
+        .iter()
+        .map(|&(name, build)| {
+            let m = Bencher::new(opts, ITERATIONS).warmup(2).run(|| {
+                black_box(build(black_box(&fragments)));
+            });
+            (name, m)
+        })
+        .collect();
+
+    let p = opts.precision;
+    let baseline = results[2].1.ops_per_sec();
+    println!("Benchmark: String concatenation ({} fragments)", args.fragments);
+    println!("Constructed length: {} bytes", expected.len());
+    println!("Iterations: {}", ITERATIONS);
+    println!("{:<26} {:<14} {:<10}", "Strategy", "µs/string", "vs with_capacity");
+    for (name, m) in &results {
+        println!(
+            "{:<26} {:<14.p$} {:.p$}x",
+            name,
+            1_000_000.0 / m.ops_per_sec(),
+            baseline / m.ops_per_sec()
+        );
+    }
+    for (name, m) in &results {
+        m.print_notes(name, p);
+    }
+}
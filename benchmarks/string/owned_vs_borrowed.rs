@@ -0,0 +1,79 @@
+// String Processing Benchmark: owned vs borrowed
+// Uppercases every word of the regex corpus, once allocating a String per
+// word and once rewriting a single reused byte buffer in place
+use clap::Parser;
+use harness::{Bencher, Options};
+use std::fs;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::process;
+
+const ITERATIONS: usize = 100000;
+
+#[derive(Parser)]
+#[command(about = "Owned vs in-place string processing benchmark")]
+struct Args {
+    /// Text corpus to process; relative to the working directory, and the
+    /// default expects to be run from benchmarks/
+    #[arg(long, default_value = "../packages/regex/bench_data.txt")]
+    corpus: PathBuf,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// One new String per word
+fn uppercase_owned(text: &str) -> Vec<String> {
+    text.split_whitespace().map(|w| w.to_ascii_uppercase()).collect()
+}
+
+// Rewrites one reused buffer in place and counts the words borrowed from it
+fn uppercase_in_place(text: &str, buf: &mut Vec<u8>) -> usize {
+    buf.clear();
+    buf.extend_from_slice(text.as_bytes());
+    buf.make_ascii_uppercase();
+    buf.split(|b| b.is_ascii_whitespace()).filter(|w| !w.is_empty()).count()
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let text = fs::read_to_string(&args.corpus).unwrap_or_else(|e| {
+        eprintln!("Cannot read corpus {}: {} (run from benchmarks/ or pass --corpus)", args.corpus.display(), e);
+        process::exit(2);
+    });
+
+    // Both strategies must produce the same words
+    let owned_words = uppercase_owned(&text);
+    let mut buf = Vec::with_capacity(text.len());
+    let in_place_count = uppercase_in_place(&text, &mut buf);
+    let in_place_words: Vec<&[u8]> = buf
+        .split(|b| b.is_ascii_whitespace())
+        .filter(|w| !w.is_empty())
+        .collect();
+    assert_eq!(owned_words.len(), in_place_count, "Word count mismatch");
+    assert!(
+        owned_words.iter().zip(&in_place_words).all(|(a, b)| a.as_bytes() == *b),
+        "Uppercased words differ"
+    );
+
+    let owned = Bencher::new(opts, ITERATIONS).warmup(100).run(|| {
+        black_box(uppercase_owned(black_box(&text)));
+    });
+    let in_place = Bencher::new(opts, ITERATIONS).warmup(100).run(|| {
+        black_box(uppercase_in_place(black_box(&text), &mut buf));
+    });
+
+    let p = opts.precision;
+    let owned_us = owned.per_iter().as_secs_f64() * 1_000_000.0;
+    let in_place_us = in_place.per_iter().as_secs_f64() * 1_000_000.0;
+
+    println!("Benchmark: String processing (owned vs borrowed)");
+    println!("Corpus: {} bytes, {} words", text.len(), owned_words.len());
+    println!("Iterations: {}", ITERATIONS);
+    println!("Owned (String per word):  {:.p$}µs/iter", owned_us);
+    println!("In-place (reused buffer): {:.p$}µs/iter", in_place_us);
+    println!("Allocation overhead:      {:.p$}x", owned_us / in_place_us);
+    owned.print_notes("Owned", p);
+    in_place.print_notes("In-place", p);
+}
@@ -0,0 +1,93 @@
+// UTF-8 Validation Benchmark: std::str::from_utf8 throughput
+// Repeats the regex corpus into one large buffer and validates it whole; with
+// --invalid, stray 0xFF bytes are planted through it and validation resumes
+// after each error, as a lossy decoder would
+use clap::Parser;
+use harness::{in_range, Bencher, Options};
+use std::fs;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::process;
+
+const ITERATIONS: usize = 200;
+
+#[derive(Parser)]
+#[command(about = "UTF-8 validation throughput benchmark")]
+struct Args {
+    /// Text corpus repeated to fill the buffer; relative to the working
+    /// directory, and the default expects to be run from benchmarks/
+    #[arg(long, default_value = "../packages/regex/bench_data.txt")]
+    corpus: PathBuf,
+
+    /// Buffer size in MiB
+    #[arg(long, value_name = "MIB", default_value_t = 16, value_parser = in_range(1usize, 1024))]
+    size: usize,
+
+    /// Plant an invalid byte every --invalid-every bytes to time the error path
+    #[arg(long)]
+    invalid: bool,
+
+    /// Distance between planted invalid bytes
+    #[arg(long, value_name = "BYTES", default_value_t = 4096, requires = "invalid", value_parser = in_range(2usize, 1 << 30))]
+    invalid_every: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// Validates the whole buffer, skipping past each invalid sequence; returns
+// the number of errors found
+fn validate(mut bytes: &[u8]) -> usize {
+    let mut errors = 0;
+    loop {
+        match std::str::from_utf8(bytes) {
+            Ok(_) => return errors,
+            Err(e) => {
+                errors += 1;
+                let skip = e.valid_up_to() + e.error_len().unwrap_or(bytes.len() - e.valid_up_to());
+                bytes = &bytes[skip..];
+            }
+        }
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let text = fs::read(&args.corpus).unwrap_or_else(|e| {
+        eprintln!("Cannot read corpus {}: {} (run from benchmarks/ or pass --corpus)", args.corpus.display(), e);
+        process::exit(2);
+    });
+    assert!(std::str::from_utf8(&text).is_ok(), "Corpus is not valid UTF-8");
+
+    let size = args.size << 20;
+    let mut buf: Vec<u8> = text.iter().copied().cycle().take(size).collect();
+    // Cutting the last repeat short may split a multi-byte character
+    let valid_len = std::str::from_utf8(&buf).map_or_else(|e| e.valid_up_to(), |s| s.len());
+    buf.truncate(valid_len);
+
+    let mut planted = 0;
+    if args.invalid {
+        for i in (args.invalid_every..buf.len()).step_by(args.invalid_every) {
+            buf[i] = 0xFF;
+            planted += 1;
+        }
+    }
+    let errors = validate(&buf);
+    assert!(errors >= planted, "Found {} errors, planted {}", errors, planted);
+
+    let m = Bencher::new(opts, ITERATIONS).warmup(5).run(|| {
+        black_box(validate(black_box(&buf)));
+    });
+
+    let p = opts.precision;
+    let mb_per_sec = buf.len() as f64 / 1_000_000.0 * m.ops_per_sec();
+    println!("Benchmark: UTF-8 validation (std::str::from_utf8)");
+    println!("Buffer: {} bytes", buf.len());
+    if args.invalid {
+        println!("Invalid sequences: {} (one per {} bytes)", errors, args.invalid_every);
+    }
+    println!("Time: {:.p$}µs/pass", m.per_iter().as_secs_f64() * 1_000_000.0);
+    println!("Throughput: {:.p$} MB/s", mb_per_sec);
+    m.print_notes("Time", p);
+}
@@ -0,0 +1,129 @@
+// UUID Benchmark: uuid crate v4 (random) generation, optionally v7
+// Generates --count UUIDs per iteration with Uuid::new_v4 (122 random bits)
+// and, with --v7, Uuid::now_v7 (a millisecond timestamp plus a counter and
+// random bits, ordered within the process). The crate is built without its
+// `fast-rng` feature, so every UUID's randomness comes from the OS via
+// getrandom, as Python's uuid4 gets it from os.urandom.
+// --strings also times formatting the generated UUIDs to hyphenated
+// lowercase Strings, the form most services store and send, so generation
+// and formatting costs show up separately.
+use clap::Parser;
+use harness::{in_range, Bencher, Measurement, Options};
+use std::collections::HashSet;
+use std::hint::black_box;
+use uuid::{Uuid, Variant, Version};
+
+const ITERATIONS: usize = 20;
+
+#[derive(Parser)]
+#[command(about = "UUID generation throughput benchmark")]
+struct Args {
+    /// UUIDs generated per iteration
+    #[arg(long, default_value_t = 100_000, value_parser = in_range(1usize, 100_000_000))]
+    count: usize,
+
+    /// Also generate time-ordered v7 UUIDs
+    #[arg(long)]
+    v7: bool,
+
+    /// Also time formatting the UUIDs to hyphenated Strings
+    #[arg(long)]
+    strings: bool,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn generate(count: usize, new: fn() -> Uuid) -> Vec<Uuid> {
+    (0..count).map(|_| new()).collect()
+}
+
+fn format_all(ids: &[Uuid]) -> Vec<String> {
+    ids.iter().map(|id| id.hyphenated().to_string()).collect()
+}
+
+// Right version and RFC 9562 variant, no duplicates, and for v7 every UUID
+// sorts after the one before it
+fn check(ids: &[Uuid], version: Version) {
+    for id in ids {
+        assert_eq!(id.get_version(), Some(version), "{} is not a {:?} UUID", id, version);
+        assert_eq!(id.get_variant(), Variant::RFC4122, "{} has the wrong variant", id);
+    }
+    let unique: HashSet<&Uuid> = ids.iter().collect();
+    assert_eq!(unique.len(), ids.len(), "Duplicate {:?} UUIDs", version);
+    if version == Version::SortRand {
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]), "v7 UUIDs out of order");
+    }
+}
+
+struct Row {
+    name: &'static str,
+    generate: Measurement,
+    format: Option<Measurement>,
+}
+
+fn measure(args: &Args, name: &'static str, version: Version, new: fn() -> Uuid) -> Row {
+    let opts = &args.harness;
+    let ids = generate(args.count, new);
+    check(&ids, version);
+    let strings = format_all(&ids);
+    assert!(
+        strings.iter().zip(&ids).all(|(s, id)| s.len() == 36 && Uuid::parse_str(s).ok() == Some(*id)),
+        "{} strings do not round-trip",
+        name
+    );
+
+    let generate_m = Bencher::new(opts, ITERATIONS).warmup(2).run(|| {
+        black_box(generate(args.count, new));
+    });
+    let format_m = args.strings.then(|| {
+        Bencher::new(opts, ITERATIONS).warmup(2).run(|| {
+            black_box(format_all(black_box(&ids)));
+        })
+    });
+    Row {
+        name,
+        generate: generate_m,
+        format: format_m,
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let mut rows = vec![measure(&args, "v4 (random)", Version::Random, Uuid::new_v4)];
+    if args.v7 {
+        rows.push(measure(&args, "v7 (time-ordered)", Version::SortRand, Uuid::now_v7));
+    }
+
+    let p = args.harness.precision;
+    let rate = |m: &Measurement| args.count as f64 * m.ops_per_sec();
+    let ns_each = |m: &Measurement| m.per_iter().as_secs_f64() * 1e9 / args.count as f64;
+    println!("Benchmark: UUID generation ({} per iteration)", args.count);
+    println!("Iterations: {}", ITERATIONS);
+    for row in &rows {
+        println!(
+            "{:<18} {:.0} UUIDs/sec ({:.p$} ns each)",
+            row.name,
+            rate(&row.generate),
+            ns_each(&row.generate)
+        );
+        if let Some(format) = &row.format {
+            println!(
+                "{:<18} {:.0} strings/sec ({:.p$} ns each)",
+                "  to String",
+                rate(format),
+                ns_each(format)
+            );
+        }
+    }
+    if let [v4, v7] = &rows[..] {
+        println!("v7 vs v4: {:.p$}x", rate(&v7.generate) / rate(&v4.generate));
+    }
+    for row in &rows {
+        row.generate.print_notes(row.name, p);
+        if let Some(format) = &row.format {
+            format.print_notes(&format!("{} to String", row.name), p);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// Vec Retain Benchmark: retain in place vs filter-collect
+// Removes a --remove fraction of a large Vec<u64> three ways: `retain`,
+// `into_iter().filter().collect()`, and `iter().filter().copied().collect()`
+// from a borrowed Vec. std collects the owned form back into the source
+// buffer (in-place iteration), so the borrowed form is the one that really
+// pays for a new allocation. Each iteration gets a fresh copy of the input,
+// built outside the timed region.
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 200;
+
+#[derive(Parser)]
+#[command(about = "Vec::retain vs filter-collect benchmark")]
+struct Args {
+    /// Elements in the Vec
+    #[arg(long, default_value_t = 1_000_000, value_parser = in_range(1usize, 1_000_000_000))]
+    n: usize,
+
+    /// Fraction of elements to remove
+    #[arg(long, default_value_t = 0.5, value_parser = in_range(0.0, 1.0))]
+    remove: f64,
+
+    /// Seed for the elements
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let mut rng = XorShift64::new(args.seed);
+    let input: Vec<u64> = (0..args.n).map(|_| rng.next_u64()).collect();
+
+    // Uniform random values, so this drops --remove of them on average
+    let cutoff = (args.remove * 10_000.0) as u64;
+    let keep = move |x: &u64| x % 10_000 >= cutoff;
+
+    let mut retained = input.clone();
+    retained.retain(keep);
+    let filtered: Vec<u64> = input.clone().into_iter().filter(keep).collect();
+    let copied: Vec<u64> = input.iter().filter(|x| keep(x)).copied().collect();
+    assert_eq!(retained, filtered, "retain and into_iter().filter() differ");
+    assert_eq!(retained, copied, "retain and iter().filter().copied() differ");
+
+    let retain = Bencher::new(opts, ITERATIONS).warmup(5).run_with_setup(
+        || input.clone(),
+        |mut v| {
+            v.retain(keep);
+            black_box(v);
+        },
+    );
+    let filter_owned = Bencher::new(opts, ITERATIONS).warmup(5).run_with_setup(
+        || input.clone(),
+        |v| {
+            black_box(v.into_iter().filter(keep).collect::<Vec<u64>>());
+        },
+    );
+    let filter_borrowed = Bencher::new(opts, ITERATIONS).warmup(5).run(|| {
+        black_box(black_box(&input).iter().filter(|x| keep(x)).copied().collect::<Vec<u64>>());
+    });
+
+    let p = opts.precision;
+    let rate = |ops_per_sec: f64| args.n as f64 * ops_per_sec;
+    let retain_rate = rate(retain.ops_per_sec());
+
+    println!("Benchmark: Vec retain vs filter-collect ({} u64 elements)", args.n);
+    println!("Removed: {} of {} ({:.p$}%)", args.n - retained.len(), args.n, args.remove * 100.0);
+    println!("Iterations: {}", ITERATIONS);
+    println!("{:<36} {:<18} {:<10}", "Strategy", "elements/sec", "vs retain");
+    for (name, m) in [
+        ("retain (in place)", &retain),
+        ("into_iter().filter().collect()", &filter_owned),
+        ("iter().filter().copied().collect()", &filter_borrowed),
+    ] {
+        let r = rate(m.ops_per_sec());
+        println!("{:<36} {:<18.0} {:.p$}x", name, r, r / retain_rate);
+        m.print_notes(name, p);
+    }
+}
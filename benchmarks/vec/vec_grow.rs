@@ -0,0 +1,78 @@
+// Vec Growth Benchmark: Vec::new() vs Vec::with_capacity(n)
+// Pushes N elements into a fresh Vec each iteration, once letting it double
+// its way up (log2(N) reallocations and copies) and once sized up front
+use clap::Parser;
+use harness::{in_range, Bencher, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 1000;
+
+#[derive(Parser)]
+#[command(about = "Vec growth vs pre-sized Vec benchmark")]
+struct Args {
+    /// Elements pushed per iteration
+    #[arg(long, default_value_t = 100_000, value_parser = in_range(1usize, 100_000_000))]
+    n: usize,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// Growth from empty: capacity doubles each time it runs out
+fn push_growing(n: usize) -> Vec<u64> {
+    let mut v = Vec::new();
+    for i in 0..n as u64 {
+        v.push(i);
+    }
+    v
+}
+
+// One allocation, no copies
+fn push_presized(n: usize) -> Vec<u64> {
+    let mut v = Vec::with_capacity(n);
+    for i in 0..n as u64 {
+        v.push(i);
+    }
+    v
+}
+
+// Capacity changes seen while growing one Vec to n elements
+fn reallocations(n: usize) -> usize {
+    let mut v: Vec<u64> = Vec::new();
+    let mut count = 0;
+    for i in 0..n as u64 {
+        let capacity = v.capacity();
+        v.push(i);
+        if v.capacity() != capacity {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+
+    assert_eq!(push_growing(args.n), push_presized(args.n), "Vec contents differ");
+
+    let growing = Bencher::new(opts, ITERATIONS).warmup(20).run(|| {
+        black_box(push_growing(black_box(args.n)));
+    });
+    let presized = Bencher::new(opts, ITERATIONS).warmup(20).run(|| {
+        black_box(push_presized(black_box(args.n)));
+    });
+
+    let p = opts.precision;
+    let growing_rate = args.n as f64 * growing.ops_per_sec();
+    let presized_rate = args.n as f64 * presized.ops_per_sec();
+
+    println!("Benchmark: Vec growth ({} u64 pushes per iteration)", args.n);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Reallocations from Vec::new(): {}", reallocations(args.n));
+    println!("Vec::new():            {:.0} elements/sec", growing_rate);
+    println!("Vec::with_capacity(n): {:.0} elements/sec", presized_rate);
+    println!("Pre-sizing speedup: {:.p$}x", presized_rate / growing_rate);
+    growing.print_notes("Vec::new()", p);
+    presized.print_notes("Vec::with_capacity(n)", p);
+}
@@ -0,0 +1,92 @@
+// Rolling Window Benchmark: rolling sum/mean over a long i64 series
+// A ring buffer of the last --window values keeps a running sum, O(1) per
+// element; the naive variant re-adds the whole window at every step, O(window)
+// per element. Integer sums make the two outputs exactly comparable.
+use clap::Parser;
+use harness::rng::XorShift64;
+use harness::{in_range, Bencher, Options};
+use std::hint::black_box;
+
+const ITERATIONS: usize = 20;
+
+#[derive(Parser)]
+#[command(about = "Rolling window aggregate benchmark")]
+struct Args {
+    /// Series length
+    #[arg(long, default_value_t = 1_000_000, value_parser = in_range(1usize, 1_000_000_000))]
+    n: usize,
+
+    /// Window size
+    #[arg(long, default_value_t = 64, value_parser = in_range(1usize, 1_000_000))]
+    window: usize,
+
+    /// Seed for the series
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[command(flatten)]
+    harness: Options,
+}
+
+// One sum per full window, ending at each index from window - 1 on
+fn rolling_ring(series: &[i64], window: usize) -> Vec<i64> {
+    let mut ring = vec![0i64; window];
+    let mut sum = 0i64;
+    let mut out = Vec::with_capacity(series.len().saturating_sub(window - 1));
+    for (i, &x) in series.iter().enumerate() {
+        let slot = i % window;
+        sum += x - ring[slot];
+        ring[slot] = x;
+        if i + 1 >= window {
+            out.push(sum);
+        }
+    }
+    out
+}
+
+fn rolling_naive(series: &[i64], window: usize) -> Vec<i64> {
+    series.windows(window).map(|w| w.iter().sum()).collect()
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    if args.window > args.n {
+        eprintln!("--window {} is longer than the series (--n {})", args.window, args.n);
+        std::process::exit(2);
+    }
+
+    // Prices moving by -50..=50 per step
+    let mut rng = XorShift64::new(args.seed);
+    let mut price = 10_000i64;
+    let series: Vec<i64> = (0..args.n)
+        .map(|_| {
+            price += rng.below(101) as i64 - 50;
+            price
+        })
+        .collect();
+
+    let sums = rolling_ring(&series, args.window);
+    assert_eq!(sums, rolling_naive(&series, args.window), "Ring buffer and naive sums differ");
+    let last_mean = sums.last().map_or(0.0, |&s| s as f64 / args.window as f64);
+
+    let ring = Bencher::new(opts, ITERATIONS).warmup(2).run(|| {
+        black_box(rolling_ring(black_box(&series), args.window));
+    });
+    let naive = Bencher::new(opts, ITERATIONS).warmup(2).run(|| {
+        black_box(rolling_naive(black_box(&series), args.window));
+    });
+
+    let p = opts.precision;
+    let ring_rate = args.n as f64 * ring.ops_per_sec();
+    let naive_rate = args.n as f64 * naive.ops_per_sec();
+
+    println!("Benchmark: Rolling sum/mean ({} elements, window {})", args.n, args.window);
+    println!("Iterations: {}", ITERATIONS);
+    println!("Last window mean: {:.p$}", last_mean);
+    println!("Ring buffer:      {:.0} elements/sec", ring_rate);
+    println!("Naive recompute:  {:.0} elements/sec", naive_rate);
+    println!("Ring buffer speedup: {:.p$}x", ring_rate / naive_rate);
+    ring.print_notes("Ring buffer", p);
+    naive.print_notes("Naive recompute", p);
+}
@@ -0,0 +1,77 @@
+// RegexSet Benchmark: classify every corpus line by which patterns it matches,
+// in one pass of a RegexSet instead of one search per pattern
+use clap::Parser;
+use harness::{Bencher, Corpus, Options};
+use regex::{Regex, RegexSet};
+use std::hint::black_box;
+
+#[derive(Parser)]
+#[command(about = "Rust RegexSet multi-pattern classification benchmark")]
+struct Args {
+    #[command(flatten)]
+    harness: Options,
+}
+
+const ITERATIONS: usize = 10000;
+
+// Same patterns as bench_rust.rs
+const PATTERNS: &[(&str, &str)] = &[
+    ("Email", r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}"),
+    ("URL", r"https?://[^\s]+"),
+    ("Phone", r"\(\d{3}\)\s?\d{3}-\d{4}|\d{3}-\d{3}-\d{4}"),
+    ("Digits", r"\d+"),
+    ("Word Boundary", r"\b[a-z]{4,}\b"),
+    ("Date ISO", r"\d{4}-\d{2}-\d{2}"),
+    ("IPv4", r"\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}\b"),
+    ("Hex Color", r"#[0-9a-fA-F]{6}"),
+    ("Version", r"v?\d+\.\d+\.\d+"),
+    ("Alphanumeric", r"[a-z]+\d+"),
+];
+
+// Lines matched by each pattern
+fn classify(set: &RegexSet, lines: &[&str]) -> Vec<usize> {
+    let mut counts = vec![0; set.len()];
+    for line in lines {
+        for i in set.matches(line).iter() {
+            counts[i] += 1;
+        }
+    }
+    counts
+}
+
+fn main() {
+    let args = Args::parse();
+    let opts = &args.harness;
+    let corpus = Corpus::load("bench_data.txt").expect("Failed to read bench_data.txt");
+    let lines: Vec<&str> = corpus.text().lines().collect();
+
+    let set = RegexSet::new(PATTERNS.iter().map(|(_, p)| p)).expect("Failed to build RegexSet");
+    let counts = classify(&set, &lines);
+
+    // The set must agree with running each regex on its own
+    for ((name, pattern), &count) in PATTERNS.iter().zip(&counts) {
+        let regex = Regex::new(pattern).unwrap();
+        let expected = lines.iter().filter(|line| regex.is_match(line)).count();
+        assert_eq!(count, expected, "RegexSet and Regex disagree on {}", name);
+    }
+
+    let m = Bencher::new(opts, ITERATIONS).warmup(100).run(|| {
+        black_box(classify(&set, black_box(&lines)));
+    });
+
+    let p = opts.precision;
+    println!("{}", "=".repeat(70));
+    println!("Rust RegexSet Benchmark ({} patterns, {} lines)", PATTERNS.len(), lines.len());
+    println!("{}", "=".repeat(70));
+    println!("{:<20} {:<10} {:<10}", "Pattern", "Lines", "Share");
+    println!("{}", "-".repeat(70));
+    for ((name, _), count) in PATTERNS.iter().zip(&counts) {
+        let share = *count as f64 / lines.len() as f64 * 100.0;
+        println!("{:<20} {:<10} {:<10}", name, count, format!("{:.p$}%", share));
+    }
+    println!("{}", "-".repeat(70));
+    println!("Avg per pass: {:.p$}µs", m.per_iter().as_secs_f64() * 1_000_000.0);
+    println!("Lines/sec: {:.0}", lines.len() as f64 * m.ops_per_sec());
+    m.print_notes("Notes", p);
+    println!("{}", "=".repeat(70));
+}
@@ -1,12 +1,343 @@
-use regex::Regex;
-use std::fs;
-use std::time::Instant;
+use clap::{CommandFactory, Parser};
+use harness::{
+    print_comparison, print_row, print_summary, Bencher, Corpus, Format, Measurement, Sibling, Suite, SuiteOptions,
+};
+use memmap2::Mmap;
+use regex::{Regex, RegexBuilder};
+use std::fs::File;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::process;
 
-fn load_data() -> String {
-    fs::read_to_string("bench_data.txt").expect("Failed to read bench_data.txt")
+#[derive(Parser)]
+#[command(about = "Rust regex benchmark")]
+struct Args {
+    #[command(flatten)]
+    harness: SuiteOptions,
+
+    /// Text corpus to search
+    #[arg(long, value_name = "PATH", default_value = DEFAULT_CORPUS)]
+    corpus: PathBuf,
+
+    /// Map the corpus read-only instead of reading it into memory, and match
+    /// over the raw bytes with regex::bytes::Regex
+    #[arg(long, conflicts_with_all = ["include_io", "manual_advance", "anchored", "split", "word_boundary"])]
+    mmap: bool,
+
+    /// Also compare find_iter against a manual find_at advance loop
+    #[arg(long, conflicts_with = "format")]
+    manual_advance: bool,
+
+    /// Also compare ^-anchored against unanchored search over the corpus lines
+    #[arg(long, conflicts_with = "format")]
+    anchored: bool,
+
+    /// With --anchored, run the anchored pattern once over the whole corpus in
+    /// multi-line mode instead of line by line
+    #[arg(long, requires = "anchored")]
+    multi_line: bool,
+
+    /// Also time Regex::split over the corpus and report fields/sec
+    #[arg(long, conflicts_with = "format")]
+    split: bool,
+
+    /// Also compare Unicode \b against ASCII (?-u:\b) in the patterns that use it
+    #[arg(long, conflicts_with = "format")]
+    word_boundary: bool,
+
+    /// Also run the Python and Go regex benchmarks and print their average
+    /// times next to Rust's
+    #[arg(long, conflicts_with = "format")]
+    compare_langs: bool,
+
+    /// Command for the Python sibling under --compare-langs
+    #[arg(long, default_value = "python3 bench_python.py")]
+    python_cmd: String,
+
+    /// Command for the Go sibling under --compare-langs
+    #[arg(long, default_value = "./bench_go")]
+    go_cmd: String,
+}
+
+// 100K iterations for all patterns (matches Python and Go)
+const ITERATIONS: usize = 100000;
+
+const DEFAULT_CORPUS: &str = "bench_data.txt";
+
+// Name, pattern, and its match count in DEFAULT_CORPUS (cross-checked with grep -oP)
+const PATTERNS: &[(&str, &str, usize)] = &[
+    ("Email", r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}", 1),
+    ("URL", r"https?://[^\s]+", 1),
+    ("Phone", r"\(\d{3}\)\s?\d{3}-\d{4}|\d{3}-\d{3}-\d{4}", 2),
+    ("Digits", r"\d+", 67),
+    ("Word Boundary", r"\b[a-z]{4,}\b", 37),
+    ("Date ISO", r"\d{4}-\d{2}-\d{2}", 4),
+    ("IPv4", r"\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}\b", 1),
+    ("Hex Color", r"#[0-9a-fA-F]{6}", 0),
+    ("Version", r"v?\d+\.\d+\.\d+", 2),
+    ("Alphanumeric", r"[a-z]+\d+", 10),
+];
+
+// Delimiter patterns for --split
+const SPLIT_PATTERNS: &[(&str, &str)] = &[
+    ("Whitespace", r"\s+"),
+    ("Punctuation", r"[,;:.!?]+\s*"),
+    ("Line", r"\r?\n"),
+];
+
+fn load_data(path: &Path) -> Corpus {
+    Corpus::load(path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e))
+}
+
+// Pages come in from disk as the search touches them, so a corpus larger
+// than memory can still be benchmarked
+fn map_corpus(path: &Path) -> Mmap {
+    let file = File::open(path).unwrap_or_else(|e| panic!("Failed to open {}: {}", path.display(), e));
+    // SAFETY: the corpus is not modified while the benchmark runs; if another
+    // process truncates it the mapping can fault, as documented by memmap2
+    unsafe { Mmap::map(&file) }.unwrap_or_else(|e| panic!("Failed to map {}: {}", path.display(), e))
+}
+
+// `expected` is the stored match count when searching DEFAULT_CORPUS; any
+// other corpus is checked against the manual find_at advance instead
+fn benchmark_pattern(
+    opts: &SuiteOptions,
+    name: &str,
+    pattern: &str,
+    expected: Option<usize>,
+    corpus: &Corpus,
+    iterations: usize,
+) -> Result<Measurement, String> {
+    let regex = match Regex::new(pattern) {
+        Ok(r) => r,
+        Err(e) => {
+            if opts.format == Format::Table {
+                print_row(&format!("{:<20} COMPILE FAILED", name));
+            }
+            return Err(format!("compile failed: {}", e));
+        }
+    };
+
+    // Benchmark (100 warmup iterations first unless --no-warmup)
+    let bencher = Bencher::new(opts, iterations).warmup(100);
+    let text = corpus.text();
+    let measurement = if opts.include_io {
+        bencher.run(|| {
+            let text = corpus.fetch(opts);
+            let _: Vec<_> = regex.find_iter(&text).collect();
+        })
+    } else {
+        bencher.run(|| {
+            let _: Vec<_> = regex.find_iter(text).collect();
+        })
+    };
+
+    // Count matches after timing so --no-warmup sees a cold regex
+    let match_count = regex.find_iter(text).count();
+    let expected = expected.unwrap_or_else(|| count_manual(&regex, text));
+    check_count(opts, name, match_count, expected)?;
+    print_pattern_row(opts, name, match_count, &measurement);
+    Ok(measurement)
+}
+
+// --mmap: the same search over the mapped bytes. regex::bytes matches
+// arbitrary bytes, so the corpus is never checked for valid UTF-8 and the
+// Unicode classes still apply to any valid UTF-8 inside it
+fn benchmark_pattern_bytes(
+    opts: &SuiteOptions,
+    name: &str,
+    pattern: &str,
+    expected: Option<usize>,
+    bytes: &[u8],
+    iterations: usize,
+) -> Result<Measurement, String> {
+    let regex = match regex::bytes::Regex::new(pattern) {
+        Ok(r) => r,
+        Err(e) => {
+            if opts.format == Format::Table {
+                print_row(&format!("{:<20} COMPILE FAILED", name));
+            }
+            return Err(format!("compile failed: {}", e));
+        }
+    };
+
+    let measurement = Bencher::new(opts, iterations).warmup(100).run(|| {
+        let _: Vec<_> = regex.find_iter(bytes).collect();
+    });
+
+    let match_count = regex.find_iter(bytes).count();
+    let expected = expected.unwrap_or_else(|| count_manual_bytes(&regex, bytes));
+    check_count(opts, name, match_count, expected)?;
+    print_pattern_row(opts, name, match_count, &measurement);
+    Ok(measurement)
+}
+
+fn check_count(opts: &SuiteOptions, name: &str, match_count: usize, expected: usize) -> Result<(), String> {
+    if match_count == expected {
+        return Ok(());
+    }
+    if opts.format == Format::Table {
+        print_row(&format!("{:<20} MISMATCH", name));
+    }
+    Err(format!("{} matches, expected {}", match_count, expected))
+}
+
+fn print_pattern_row(opts: &SuiteOptions, name: &str, match_count: usize, measurement: &Measurement) {
+    if opts.format != Format::Table {
+        return;
+    }
+
+    let p = opts.precision;
+    let total_ms = measurement.total().as_secs_f64() * 1000.0;
+    let avg_us = measurement.per_iter().as_secs_f64() * 1_000_000.0;
+    let iters_per_sec = measurement.ops_per_sec();
+
+    let row = format!(
+        "{:<20} {:<10} {:<12.p$} {:<12.p$} {:<12.0}",
+        name, match_count, avg_us, total_ms, iters_per_sec
+    );
+    let notes = measurement.notes(p);
+    if notes.is_empty() {
+        print_row(&row);
+    } else {
+        print_row(&format!("{} ({})", row, notes.join(", ")));
+    }
+}
+
+// Equivalent of find_iter().count() driven by find_at, advancing past each match by hand
+fn count_manual(regex: &Regex, text: &str) -> usize {
+    let mut count = 0;
+    let mut offset = 0;
+    while let Some(m) = regex.find_at(text, offset) {
+        count += 1;
+        offset = if m.end() > m.start() {
+            m.end()
+        } else {
+            // Step over the character after an empty match
+            match text[m.end()..].chars().next() {
+                Some(c) => m.end() + c.len_utf8(),
+                None => break,
+            }
+        };
+    }
+    count
+}
+
+// count_manual over bytes; an empty match steps over a single byte
+fn count_manual_bytes(regex: &regex::bytes::Regex, bytes: &[u8]) -> usize {
+    let mut count = 0;
+    let mut offset = 0;
+    while let Some(m) = regex.find_at(bytes, offset) {
+        count += 1;
+        offset = if m.end() > m.start() { m.end() } else { m.end() + 1 };
+        if offset > bytes.len() {
+            break;
+        }
+    }
+    count
+}
+
+// Failures are returned for main to report; --check-only stops after the count check
+fn benchmark_pattern_manual(
+    opts: &SuiteOptions,
+    name: &str,
+    pattern: &str,
+    text: &str,
+    iterations: usize,
+) -> Result<(), String> {
+    let regex = Regex::new(pattern).map_err(|e| format!("compile failed: {}", e))?;
+
+    let iter_count = regex.find_iter(text).count();
+    let manual_count = count_manual(&regex, text);
+    if iter_count != manual_count {
+        return Err(format!("MISMATCH (find_iter {} vs manual {})", iter_count, manual_count));
+    }
+    if opts.check_only {
+        return Ok(());
+    }
+
+    let find_iter = Bencher::new(opts, iterations).warmup(100).run(|| {
+        black_box(regex.find_iter(black_box(text)).count());
+    });
+    let manual = Bencher::new(opts, iterations).warmup(100).run(|| {
+        black_box(count_manual(&regex, black_box(text)));
+    });
+
+    let p = opts.precision;
+    let iter_us = find_iter.per_iter().as_secs_f64() * 1_000_000.0;
+    let manual_us = manual.per_iter().as_secs_f64() * 1_000_000.0;
+    println!(
+        "{:<20} {:<10} {:<14.p$} {:<14.p$} {:<8}",
+        name,
+        iter_count,
+        iter_us,
+        manual_us,
+        format!("{:.p$}x", iter_us / manual_us)
+    );
+    Ok(())
+}
+
+fn count_per_line(regex: &Regex, lines: &[&str]) -> usize {
+    lines.iter().map(|line| regex.find_iter(line).count()).sum()
+}
+
+fn benchmark_pattern_anchored(
+    opts: &SuiteOptions,
+    name: &str,
+    pattern: &str,
+    text: &str,
+    multi_line: bool,
+    iterations: usize,
+) -> Result<(), String> {
+    let anchored_pattern = format!("^(?:{})", pattern);
+    let compile_failed = |e| format!("compile failed: {}", e);
+    let regex = Regex::new(pattern).map_err(compile_failed)?;
+    let anchored = RegexBuilder::new(&anchored_pattern).multi_line(multi_line).build().map_err(compile_failed)?;
+    let lines: Vec<&str> = text.lines().collect();
+
+    // Multi-line ^ must find exactly the line-start matches the per-line search finds
+    let unanchored_count = count_per_line(&regex, &lines);
+    let anchored_count = if multi_line {
+        let whole = anchored.find_iter(text).count();
+        let per_line = count_per_line(&Regex::new(&anchored_pattern).unwrap(), &lines);
+        if whole != per_line {
+            return Err(format!("MISMATCH (multi-line {} vs per-line {})", whole, per_line));
+        }
+        whole
+    } else {
+        count_per_line(&anchored, &lines)
+    };
+    if opts.check_only {
+        return Ok(());
+    }
+
+    let unanchored_m = Bencher::new(opts, iterations).warmup(100).run(|| {
+        black_box(count_per_line(&regex, black_box(&lines)));
+    });
+    let anchored_m = Bencher::new(opts, iterations).warmup(100).run(|| {
+        if multi_line {
+            black_box(anchored.find_iter(black_box(text)).count());
+        } else {
+            black_box(count_per_line(&anchored, black_box(&lines)));
+        }
+    });
+
+    let p = opts.precision;
+    let unanchored_us = unanchored_m.per_iter().as_secs_f64() * 1_000_000.0;
+    let anchored_us = anchored_m.per_iter().as_secs_f64() * 1_000_000.0;
+    println!(
+        "{:<20} {:<6} {:<6} {:<12.p$} {:<12.p$} {:<8}",
+        name,
+        unanchored_count,
+        anchored_count,
+        unanchored_us,
+        anchored_us,
+        format!("{:.p$}x", unanchored_us / anchored_us)
+    );
+    Ok(())
 }
 
-fn benchmark_pattern(name: &str, pattern: &str, text: &str, iterations: usize) {
+fn benchmark_split(opts: &SuiteOptions, name: &str, pattern: &str, text: &str, iterations: usize) {
     let regex = match Regex::new(pattern) {
         Ok(r) => r,
         Err(_) => {
@@ -15,51 +346,231 @@ fn benchmark_pattern(name: &str, pattern: &str, text: &str, iterations: usize) {
         }
     };
 
-    // Warmup
-    for _ in 0..100 {
-        let _: Vec<_> = regex.find_iter(text).collect();
-    }
+    // Collecting the fields keeps the per-substring allocation in the measurement
+    let fields = regex.split(text).count();
+    let measurement = Bencher::new(opts, iterations).warmup(100).run(|| {
+        let _: Vec<&str> = regex.split(black_box(text)).collect();
+    });
 
-    // Count matches
-    let match_count = regex.find_iter(text).count();
+    let p = opts.precision;
+    let avg_us = measurement.per_iter().as_secs_f64() * 1_000_000.0;
+    let fields_per_sec = fields as f64 * measurement.ops_per_sec();
+    println!("{:<20} {:<10} {:<12.p$} {:<14.0}", name, fields, avg_us, fields_per_sec);
+}
 
-    // Benchmark
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _: Vec<_> = regex.find_iter(text).collect();
-    }
-    let elapsed = start.elapsed();
+fn benchmark_word_boundary(opts: &SuiteOptions, name: &str, pattern: &str, text: &str, iterations: usize) {
+    let ascii_pattern = pattern.replace(r"\b", r"(?-u:\b)");
+    let (unicode, ascii) = match (Regex::new(pattern), Regex::new(&ascii_pattern)) {
+        (Ok(u), Ok(a)) => (u, a),
+        _ => {
+            println!("{:<20} COMPILE FAILED", name);
+            return;
+        }
+    };
 
-    let total_ms = elapsed.as_secs_f64() * 1000.0;
-    let avg_us = (elapsed.as_secs_f64() / iterations as f64) * 1_000_000.0;
+    // Counts only differ if the corpus has non-ASCII word characters
+    let unicode_count = unicode.find_iter(text).count();
+    let ascii_count = ascii.find_iter(text).count();
 
+    let unicode_m = Bencher::new(opts, iterations).warmup(100).run(|| {
+        black_box(unicode.find_iter(black_box(text)).count());
+    });
+    let ascii_m = Bencher::new(opts, iterations).warmup(100).run(|| {
+        black_box(ascii.find_iter(black_box(text)).count());
+    });
+
+    let p = opts.precision;
+    let unicode_us = unicode_m.per_iter().as_secs_f64() * 1_000_000.0;
+    let ascii_us = ascii_m.per_iter().as_secs_f64() * 1_000_000.0;
     println!(
-        "{:<20} {:<10} {:<12.2} {:<12.2}",
-        name, match_count, avg_us, total_ms
+        "{:<20} {:<6} {:<6} {:<12.p$} {:<12.p$} {:<8}",
+        name,
+        unicode_count,
+        ascii_count,
+        unicode_us,
+        ascii_us,
+        format!("{:.p$}x", unicode_us / ascii_us)
     );
 }
 
+// Outcomes of the --manual-advance and --anchored cross-checks, which run
+// outside the suite: a failure is a table row, or a check line under
+// --check-only, and either way the run exits with status 1
+#[derive(Default)]
+struct ExtraChecks {
+    run: usize,
+    failed: usize,
+}
+
+impl ExtraChecks {
+    fn record(&mut self, opts: &SuiteOptions, kind: &str, name: &str, outcome: Result<(), String>) {
+        self.run += 1;
+        let check = format!("{} {}", kind, name);
+        match outcome {
+            Ok(()) if opts.check_only => println!("check {:<20} ok", check),
+            Ok(()) => {}
+            Err(message) if opts.check_only => {
+                self.failed += 1;
+                println!("check {:<20} FAILED: {}", check, message);
+            }
+            Err(message) => {
+                self.failed += 1;
+                println!("{:<20} {}", name, message);
+            }
+        }
+    }
+
+    fn finish(&self, opts: &SuiteOptions) {
+        if opts.check_only && self.run > 0 {
+            println!("Checks: {} of {} passed", self.run - self.failed, self.run);
+        }
+        if self.failed > 0 {
+            process::exit(1);
+        }
+    }
+}
+
 fn main() {
-    let text = load_data();
+    let args = Args::parse();
+    let opts = &args.harness;
+    // --mmap leaves the corpus on disk; the extra tables need it as a &str
+    // and conflict with --mmap
+    let mapped = args.mmap.then(|| map_corpus(&args.corpus));
+    let corpus = mapped.is_none().then(|| load_data(&args.corpus));
+    let text = corpus.as_ref().map_or("", Corpus::text);
 
-    println!("{}", "=".repeat(70));
-    println!("Rust Regex Benchmark (100K iterations per pattern)");
-    println!("{}", "=".repeat(70));
-    println!("{:<20} {:<10} {:<12} {:<12}", "Pattern", "Matches", "Avg (µs)", "Total (ms)");
-    println!("{}", "-".repeat(70));
+    // Machine-readable formats print only the report
+    let table = opts.format == Format::Table;
+    if table {
+        println!("{}", "=".repeat(70));
+        match opts.duration {
+            Some(budget) => println!("Rust Regex Benchmark ({:?} per pattern)", budget),
+            None => println!("Rust Regex Benchmark (100K iterations per pattern)"),
+        }
+        if let Some(mapped) = &mapped {
+            println!("Corpus: {} mapped, {} bytes (regex::bytes)", args.corpus.display(), mapped.len());
+        }
+        println!("{}", "=".repeat(70));
+        println!(
+            "{:<20} {:<10} {:<12} {:<12} {:<12}",
+            "Pattern", "Matches", "Avg (µs)", "Total (ms)", "Iters/sec"
+        );
+        println!("{}", "-".repeat(70));
+    }
 
-    // 100K iterations for all patterns (matches Python and Go)
-    benchmark_pattern("Email", r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}", &text, 100000);
-    benchmark_pattern("URL", r"https?://[^\s]+", &text, 100000);
-    benchmark_pattern("Phone", r"\(\d{3}\)\s?\d{3}-\d{4}|\d{3}-\d{3}-\d{4}", &text, 100000);
-    benchmark_pattern("Digits", r"\d+", &text, 100000);
-    benchmark_pattern("Word Boundary", r"\b[a-z]{4,}\b", &text, 100000);
-    benchmark_pattern("Date ISO", r"\d{4}-\d{2}-\d{2}", &text, 100000);
-    benchmark_pattern("IPv4", r"\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}\b", &text, 100000);
-    benchmark_pattern("Hex Color", r"#[0-9a-fA-F]{6}", &text, 100000);
-    benchmark_pattern("Version", r"v?\d+\.\d+\.\d+", &text, 100000);
-    benchmark_pattern("Alphanumeric", r"[a-z]+\d+", &text, 100000);
+    // Each pattern runs isolated: failures and panics are listed after the table
+    let mut suite = Suite::new(opts).command(Args::command());
+    // The stored counts only hold for the default corpus
+    let stored_counts = args.corpus == Path::new(DEFAULT_CORPUS);
+    for (name, pattern, count) in PATTERNS {
+        let expected = stored_counts.then_some(*count);
+        match (&mapped, &corpus) {
+            (Some(mapped), _) => {
+                let bytes: &[u8] = mapped;
+                suite.add("regex", name, move || {
+                    benchmark_pattern_bytes(opts, name, pattern, expected, bytes, ITERATIONS)
+                });
+            }
+            (None, Some(corpus)) => {
+                suite.add("regex", name, move || benchmark_pattern(opts, name, pattern, expected, corpus, ITERATIONS));
+            }
+            (None, None) => unreachable!("corpus is loaded unless mapped"),
+        }
+    }
+    let results = suite.run();
 
+    // The suite has already written the --format report
+    if !table {
+        return;
+    }
     println!("{}", "-".repeat(70));
+    print_summary(opts, &results);
+
+    // --check-only runs the cross-checks behind the extra tables and skips
+    // their timings
+    let mut extra = ExtraChecks::default();
+    if opts.check_only {
+        for (name, pattern, _) in PATTERNS.iter().filter(|_| args.manual_advance) {
+            extra.record(opts, "manual", name, benchmark_pattern_manual(opts, name, pattern, text, ITERATIONS));
+        }
+        for (name, pattern, _) in PATTERNS.iter().filter(|_| args.anchored) {
+            let outcome = benchmark_pattern_anchored(opts, name, pattern, text, args.multi_line, ITERATIONS);
+            extra.record(opts, "anchored", name, outcome);
+        }
+        extra.finish(opts);
+        println!("{}", "=".repeat(70));
+        return;
+    }
+
+    if args.word_boundary {
+        println!("Unicode \\b vs ASCII (?-u:\\b) word boundaries");
+        println!(
+            "{:<20} {:<6} {:<6} {:<12} {:<12} {:<8}",
+            "Pattern", "Uni", "ASCII", "Uni (µs)", "ASCII (µs)", "Speedup"
+        );
+        println!("{}", "-".repeat(70));
+        for (name, pattern, _) in PATTERNS.iter().filter(|(_, p, _)| p.contains(r"\b")) {
+            benchmark_word_boundary(opts, name, pattern, text, ITERATIONS);
+        }
+        println!("{}", "-".repeat(70));
+    }
+
+    if args.compare_langs {
+        // Avg (µs) is the third column of every sibling's table, after Matches
+        let rust: Vec<(String, f64)> = results
+            .iter()
+            .filter_map(|r| {
+                let m = r.outcome.as_ref().ok()?;
+                Some((r.name.clone(), m.per_iter().as_secs_f64() * 1_000_000.0))
+            })
+            .collect();
+        let siblings = [
+            Sibling::run("Python", &args.python_cmd, 20, 1),
+            Sibling::run("Go", &args.go_cmd, 20, 1),
+        ];
+        println!("Rust vs sibling languages (average per iteration)");
+        print_comparison("µs", opts.precision, &rust, &siblings);
+        println!("{}", "-".repeat(70));
+    }
+
+    if args.manual_advance {
+        println!("find_iter vs manual find_at advance (match counting)");
+        println!(
+            "{:<20} {:<10} {:<14} {:<14} {:<8}",
+            "Pattern", "Matches", "find_iter (µs)", "manual (µs)", "Speedup"
+        );
+        println!("{}", "-".repeat(70));
+        for (name, pattern, _) in PATTERNS {
+            extra.record(opts, "manual", name, benchmark_pattern_manual(opts, name, pattern, text, ITERATIONS));
+        }
+        println!("{}", "-".repeat(70));
+    }
+
+    if args.anchored {
+        let mode = if args.multi_line { "multi-line, whole corpus" } else { "per line" };
+        println!("Unanchored vs ^-anchored search ({})", mode);
+        println!(
+            "{:<20} {:<6} {:<6} {:<12} {:<12} {:<8}",
+            "Pattern", "Unanc", "Anch", "Unanc (µs)", "Anch (µs)", "Speedup"
+        );
+        println!("{}", "-".repeat(70));
+        for (name, pattern, _) in PATTERNS {
+            let outcome = benchmark_pattern_anchored(opts, name, pattern, text, args.multi_line, ITERATIONS);
+            extra.record(opts, "anchored", name, outcome);
+        }
+        println!("{}", "-".repeat(70));
+    }
+
+    if args.split {
+        println!("Regex::split field counting");
+        println!("{:<20} {:<10} {:<12} {:<14}", "Delimiter", "Fields", "Avg (µs)", "Fields/sec");
+        println!("{}", "-".repeat(70));
+        for (name, pattern) in SPLIT_PATTERNS {
+            benchmark_split(opts, name, pattern, text, ITERATIONS);
+        }
+        println!("{}", "-".repeat(70));
+    }
+
     println!("{}", "=".repeat(70));
+    extra.finish(opts);
 }